@@ -0,0 +1,237 @@
+use nom::branch::alt;
+use nom::bytes::complete::{tag, take_until};
+use nom::character::complete::line_ending;
+use nom::combinator::opt;
+use nom::error::{Error, ErrorKind};
+use nom::IResult;
+
+use crate::parser::{parse_whole_value, AssertionFailedError};
+
+/// Parses the failure messages of AssertJ
+pub fn parse(input: &str) -> IResult<&str, AssertionFailedError<'_>> {
+    alt((parse_expected_but_was, parse_expecting_actual))(input)
+}
+
+/// demo input:
+/// expected: "Hello"
+///  but was: "World"
+fn parse_expected_but_was(input: &str) -> IResult<&str, AssertionFailedError<'_>> {
+    let (input, before) = take_until("expected: ")(input)?;
+    let indent = indentation(before);
+    let (input, _) = tag("expected: ")(input)?;
+    let (input, expected) = take_until("but was: ")(input)?;
+    if !expected.trim_end_matches([' ', '\t']).ends_with('\n') {
+        return fail(input);
+    }
+    let (input, _) = tag("but was: ")(input)?;
+    let (input, real) = value_lines(input, indent);
+
+    let mut warnings = vec![];
+    let (Some(expected), Some(real)) = (
+        parse_whole_value(expected, &mut warnings),
+        parse_whole_value(real, &mut warnings),
+    ) else {
+        return fail(input);
+    };
+    Ok((
+        input,
+        AssertionFailedError {
+            expected,
+            real,
+            warnings,
+        },
+    ))
+}
+
+/// demo input:
+/// Expecting actual:
+///   User(name=b)
+/// to be equal to:
+///   User(name=a)
+/// when recursively comparing field by field, but found the following difference:
+fn parse_expecting_actual(input: &str) -> IResult<&str, AssertionFailedError<'_>> {
+    let (input, before) = take_until("Expecting")(input)?;
+    let indent = indentation(before);
+    let (input, _) = tag("Expecting")(input)?;
+    let (input, _) = opt(tag(" actual"))(input)?;
+    let (input, _) = tag(":")(input)?;
+    let (input, _) = line_ending(input)?;
+    let (input, real) = indented_lines(input, indent);
+    let (input, _) = line_ending(input)?;
+    let (input, _) = take_until("to be equal to:")(input)?;
+    let (input, _) = tag("to be equal to:")(input)?;
+    let (input, _) = line_ending(input)?;
+    let (input, expected) = indented_lines(input, indent);
+
+    let mut warnings = vec![];
+    let (Some(expected), Some(real)) = (
+        parse_whole_value(unwrap(expected), &mut warnings),
+        parse_whole_value(unwrap(real), &mut warnings),
+    ) else {
+        return fail(input);
+    };
+    Ok((
+        input,
+        AssertionFailedError {
+            expected,
+            real,
+            warnings,
+        },
+    ))
+}
+
+/// Older versions of AssertJ wrap the values in "<>"
+fn unwrap(value: &str) -> &str {
+    let value = value.trim();
+    value
+        .strip_prefix('<')
+        .and_then(|v| v.strip_suffix('>'))
+        .unwrap_or(value)
+}
+
+/// Takes the rest of the current line and the following lines that belong to the same value
+fn value_lines(input: &str, indent: usize) -> (&str, &str) {
+    let first = input.find('\n').unwrap_or(input.len());
+    let (rest, following) = indented_lines(input.get(first + 1..).unwrap_or_default(), indent);
+    let end = if following.is_empty() {
+        first
+    } else {
+        input.len() - rest.len()
+    };
+    (&input[end..], &input[..end])
+}
+
+/// Takes the lines that are indented deeper than `indent`.
+/// Stack frames, empty lines and the recursive comparison section end the value
+fn indented_lines(input: &str, indent: usize) -> (&str, &str) {
+    let mut end = 0;
+    for line in input.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        let line_indent = line.len() - trimmed.len();
+        let trimmed = trimmed.trim_end();
+        if trimmed.is_empty()
+            || line_indent <= indent
+            || trimmed.starts_with("at ")
+            || trimmed.starts_with("when recursively comparing")
+        {
+            break;
+        }
+        end += line.len();
+    }
+    let value = &input[..end];
+    let value_end = value.trim_end_matches(['\r', '\n']).len();
+    (&input[value_end..], &input[..value_end])
+}
+
+/// Amount of whitespace in front of the last line of `before`
+fn indentation(before: &str) -> usize {
+    let line = before.rsplit('\n').next().unwrap_or_default();
+    line.len() - line.trim_start().len()
+}
+
+fn fail<T>(input: &str) -> IResult<&str, T> {
+    Err(nom::Err::Error(Error::new(input, ErrorKind::Verify)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        assertj::parse,
+        parser::{AssertionFailedError, Dto, DtoField, ValueKind},
+    };
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parse_string() {
+        let input = "org.opentest4j.AssertionFailedError: \nexpected: \"Hello\"\n but was: \"World\"\n\tat com.example.UserTest.string(UserTest.java:12)";
+        let expected = AssertionFailedError {
+            expected: ValueKind::Quoted("Hello".into()),
+            real: ValueKind::Quoted("World".into()),
+            warnings: vec![],
+        };
+
+        assert_eq!(
+            parse(input),
+            Ok((
+                "\n\tat com.example.UserTest.string(UserTest.java:12)",
+                expected
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_multi_line() {
+        let input = "    expected: [\"a\",\n        \"b\"]\n     but was: [\"a\",\n        \"c\"]\n        at app//com.example.UserTest.list(UserTest.java:12)";
+        let expected = AssertionFailedError {
+            expected: ValueKind::Array(vec![
+                ValueKind::Quoted("a".into()),
+                ValueKind::Quoted("b".into()),
+            ]),
+            real: ValueKind::Array(vec![
+                ValueKind::Quoted("a".into()),
+                ValueKind::Quoted("c".into()),
+            ]),
+            warnings: vec![],
+        };
+
+        assert_eq!(
+            parse(input),
+            Ok((
+                "\n        at app//com.example.UserTest.list(UserTest.java:12)",
+                expected
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_unfinished() {
+        assert!(parse("expected: [\"a\",\n").is_err());
+        assert!(parse("expected: [\"a\",\n but was: [\"a\",").is_err());
+        assert!(parse("expected: <1> but was: <2>").is_err());
+    }
+
+    #[test]
+    fn parse_recursive_comparison() {
+        let input = "Expecting actual:
+  User(name=b)
+to be equal to:
+  User(name=a)
+when recursively comparing field by field, but found the following difference:
+
+field/property 'name' differ:
+- actual value  : \"b\"
+- expected value: \"a\"";
+        let user = |name: &'static str| {
+            ValueKind::Dto(Dto {
+                name: "User",
+                fields: vec![DtoField {
+                    name: "name".into(),
+                    value: ValueKind::String(name.into()),
+                }],
+                ..Default::default()
+            })
+        };
+
+        let (_, out) = parse(input).unwrap();
+        assert_eq!(
+            out,
+            AssertionFailedError {
+                expected: user("a"),
+                real: user("b"),
+                warnings: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_legacy_expecting() {
+        let input = "Expecting:\n <\"World\">\nto be equal to:\n <\"Hello\">\nbut was not.";
+        let expected = AssertionFailedError {
+            expected: ValueKind::Quoted("Hello".into()),
+            real: ValueKind::Quoted("World".into()),
+            warnings: vec![],
+        };
+
+        assert_eq!(parse(input), Ok(("\nbut was not.", expected)));
+    }
+}
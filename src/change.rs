@@ -0,0 +1,264 @@
+use crate::{
+    diff::{self, Difference},
+    parser::ValueKind,
+    DiffOptions,
+};
+
+/// A difference of the compared values at one path
+#[derive(Debug, PartialEq, Clone)]
+pub struct Change<'a> {
+    /// Where the values differ: $.e["eee"].a. Lists are indexed by the expected index,
+    /// only added values by the real one
+    pub path: String,
+    pub kind: ChangeKind,
+    pub expected: Option<ValueKind<'a>>,
+    pub actual: Option<ValueKind<'a>>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ChangeKind {
+    /// Same kind of value with another content
+    Changed,
+    /// Only the real value has it
+    Added,
+    /// Only the expected value has it
+    Removed,
+    /// The same list value at another index
+    Moved,
+    /// Values of different types like a number and a list
+    TypeMismatch,
+    /// Dtos of different classes
+    ClassChange,
+}
+
+/// Flattens the difference of two values into the changes at their paths
+pub fn changes<'a>(
+    expected: ValueKind<'a>,
+    actual: ValueKind<'a>,
+    options: &DiffOptions,
+) -> Vec<Change<'a>> {
+    let (difference, _) = diff::diff(expected.clone(), actual.clone(), options);
+    from_difference(difference, &expected, &actual)
+}
+
+/// The changes of a difference that was made of the values
+pub fn from_difference<'a>(
+    difference: Difference<'a>,
+    expected: &ValueKind<'a>,
+    actual: &ValueKind<'a>,
+) -> Vec<Change<'a>> {
+    let mut out = vec![];
+    collect(difference, "$", Some(expected), Some(actual), &mut out);
+    out
+}
+
+/// Walks the difference along with the values it was made of
+fn collect<'a>(
+    difference: Difference<'a>,
+    path: &str,
+    expected: Option<&ValueKind<'a>>,
+    actual: Option<&ValueKind<'a>>,
+    out: &mut Vec<Change<'a>>,
+) {
+    let change =
+        |kind, path: String, expected: Option<&ValueKind<'a>>, actual: Option<&ValueKind<'a>>| {
+            Change {
+                path,
+                kind,
+                expected: expected.cloned(),
+                actual: actual.cloned(),
+            }
+        };
+    match difference {
+        Difference::Equal => {}
+        Difference::Type(_) => out.push(change(
+            ChangeKind::TypeMismatch,
+            path.to_string(),
+            expected,
+            actual,
+        )),
+        Difference::ClassChange(_) => out.push(change(
+            ChangeKind::ClassChange,
+            path.to_string(),
+            expected,
+            actual,
+        )),
+        Difference::DtoChange((_, o)) | Difference::ArrayChange(o) => {
+            for d in o {
+                collect(d, path, expected, actual, out);
+            }
+        }
+        Difference::FieldValueChange((name, d)) => {
+            let path = format!("{path}{}", field_step(expected, &name));
+            let expected = expected.and_then(|e| field_value(e, &name));
+            let actual = actual.and_then(|a| field_value(a, &name));
+            collect(*d, &path, expected, actual, out);
+        }
+        Difference::ParentChange(d) => {
+            let expected = expected.and_then(parent);
+            let actual = actual.and_then(parent);
+            collect(*d, &format!("{path}.super"), expected, actual, out);
+        }
+        Difference::MissingField(field) => out.push(change(
+            ChangeKind::Removed,
+            format!("{path}{}", field_step(expected, &field.name)),
+            Some(&field.value),
+            None,
+        )),
+        Difference::ExtraField(field) => out.push(change(
+            ChangeKind::Added,
+            format!("{path}{}", field_step(actual, &field.name)),
+            None,
+            Some(&field.value),
+        )),
+        Difference::UndefinedRight(value) => {
+            let (path, value) = entry(path, expected, value.as_ref());
+            out.push(change(ChangeKind::Removed, path, value, None));
+        }
+        Difference::UndefinedLeft(value) => {
+            let (path, value) = entry(path, actual, value.as_ref());
+            out.push(change(ChangeKind::Added, path, None, value));
+        }
+        Difference::Removed((i, value)) => out.push(change(
+            ChangeKind::Removed,
+            format!("{path}[{i}]"),
+            Some(&value),
+            None,
+        )),
+        Difference::Inserted((j, value)) => out.push(change(
+            ChangeKind::Added,
+            format!("{path}[{j}]"),
+            None,
+            Some(&value),
+        )),
+        Difference::Moved((i, j)) => out.push(change(
+            ChangeKind::Moved,
+            format!("{path}[{i}]"),
+            expected.and_then(|e| item(e, i)),
+            actual.and_then(|a| item(a, j)),
+        )),
+        Difference::Changed((i, j, _, d)) => {
+            let expected = expected.and_then(|e| item(e, i));
+            let actual = actual.and_then(|a| item(a, j));
+            collect(*d, &format!("{path}[{i}]"), expected, actual, out);
+        }
+        Difference::Child(_)
+        | Difference::CharsEqual(_)
+        | Difference::CharsRemove(_)
+        | Difference::CharsAdd(_)
+        | Difference::FieldNameChange(_)
+        | Difference::NumberChange(_)
+        | Difference::PrecisionChange(_)
+        | Difference::TimezoneChange(_)
+        | Difference::FormatChange(_)
+        | Difference::SubtleChange(_) => out.push(change(
+            ChangeKind::Changed,
+            path.to_string(),
+            expected,
+            actual,
+        )),
+    }
+}
+
+/// Map entries are addressed by their key: ["eee"], Dto fields by their name: .a
+pub fn field_step(container: Option<&ValueKind<'_>>, name: &str) -> String {
+    match container {
+        Some(ValueKind::Map(_)) => format!("[{name:?}]"),
+        _ => format!(".{name}"),
+    }
+}
+
+/// Path and value of a map entry or parent that only one side has
+pub fn entry<'v, 'a>(
+    path: &str,
+    container: Option<&ValueKind<'a>>,
+    value: Option<&'v ValueKind<'a>>,
+) -> (String, Option<&'v ValueKind<'a>>) {
+    match value {
+        Some(ValueKind::Field(field)) => (
+            format!("{path}{}", field_step(container, &field.name)),
+            Some(&field.value),
+        ),
+        value => (path.to_string(), value),
+    }
+}
+
+pub fn field_value<'v, 'a>(value: &'v ValueKind<'a>, name: &str) -> Option<&'v ValueKind<'a>> {
+    match value {
+        ValueKind::Dto(dto) => dto.fields.iter().find(|f| f.name == name).map(|f| &f.value),
+        ValueKind::Map(entries) => entries.iter().find_map(|e| match e {
+            ValueKind::Field(f) if f.name == name => Some(&f.value),
+            _ => None,
+        }),
+        ValueKind::Field(field) if field.name == name => Some(&field.value),
+        _ => None,
+    }
+}
+
+pub fn item<'v, 'a>(value: &'v ValueKind<'a>, index: usize) -> Option<&'v ValueKind<'a>> {
+    match value {
+        ValueKind::Array(items) | ValueKind::Tuple(items) | ValueKind::Map(items) => {
+            items.get(index)
+        }
+        _ => None,
+    }
+}
+
+pub fn parent<'v, 'a>(value: &'v ValueKind<'a>) -> Option<&'v ValueKind<'a>> {
+    match value {
+        ValueKind::Dto(dto) => dto.parent.as_deref(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        change::{changes, Change, ChangeKind},
+        parser::{scalar, ValueKind},
+        test_data, DiffOptions,
+    };
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn complicated_changes() {
+        let complicated = test_data::get_complicated_expected();
+        let out = changes(
+            complicated.expected,
+            complicated.real,
+            &DiffOptions::default(),
+        );
+
+        assert_eq!(
+            out,
+            vec![Change {
+                path: "$.e[\"eee\"].a".to_string(),
+                kind: ChangeKind::Changed,
+                expected: Some(ValueKind::String("a".into())),
+                actual: Some(ValueKind::String("b".into())),
+            }]
+        );
+    }
+
+    #[test]
+    fn list_changes() {
+        let a = ValueKind::Array(vec![scalar("1".into()), scalar("2".into())]);
+        let b = ValueKind::Array(vec![
+            scalar("1".into()),
+            ValueKind::Array(vec![]),
+            scalar("3".into()),
+        ]);
+        let out = changes(a, b, &DiffOptions::default())
+            .into_iter()
+            .map(|c| (c.path, c.kind))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            out,
+            vec![
+                ("$[1]".to_string(), ChangeKind::TypeMismatch),
+                ("$[2]".to_string(), ChangeKind::Added),
+            ]
+        );
+    }
+}
@@ -1,27 +1,52 @@
 use itertools::Itertools;
 
-use crate::parser::{Dto, DtoField, ValueKind};
+use crate::parser::{split_zone, Dto, DtoField, DtoStyle, ValueKind};
 
 pub fn value_code(value: ValueKind, indent: usize) -> String {
     match value {
         ValueKind::Null => "null".to_string(),
-        ValueKind::String(s) => format!("\"{s}\""),
+        ValueKind::String(s) | ValueKind::Quoted(s) => format!("\"{s}\""),
+        ValueKind::Integer(s) | ValueKind::Decimal(s) | ValueKind::Constant(s) => s.to_string(),
+        ValueKind::Boolean(s) => s.to_lowercase(),
+        ValueKind::Uuid(s) => format!("UUID.fromString(\"{s}\")"),
+        ValueKind::DateTime(s) => date_time_code(&s),
         ValueKind::Array(a) => array_code(a, indent + 2),
         ValueKind::Map(m) => map_code(m, indent + 2),
+        ValueKind::Tuple(t) => tuple_code(t, indent),
         ValueKind::Dto(d) => dto_code(d, indent + 2),
         ValueKind::Field(f) => field_code(*f, indent),
     }
 }
 
 fn dto_code(dto: Dto, indent: usize) -> String {
+    if dto.style == DtoStyle::Brackets || dto.fields.iter().any(DtoField::is_positional) {
+        return record_code(dto, indent);
+    }
+    // @SuperBuilder sets the fields of the parent on the same builder
+    let mut fields = match dto.parent.map(|p| *p) {
+        Some(ValueKind::Dto(parent)) => parent.fields,
+        _ => vec![],
+    };
+    fields.extend(dto.fields);
     format!(
         "{}.builder()\n{}{}.build()",
         dto.name,
         " ".repeat(indent),
-        fields_code(dto.fields, indent)
+        fields_code(fields, indent)
     )
 }
 
+/// Records and Dtos without field names have no builder so the constructor is used
+fn record_code(dto: Dto, indent: usize) -> String {
+    let values = dto
+        .fields
+        .into_iter()
+        .map(|f| value_code(f.value, indent))
+        .join(", ");
+
+    format!("new {}({values})", dto.name)
+}
+
 fn fields_code(fields: Vec<DtoField<'_>>, indent: usize) -> String {
     fields
         .into_iter()
@@ -40,6 +65,25 @@ fn field_code(field: DtoField<'_>, indent: usize) -> String {
     format!("{}, {}", field.name, value_code(field.value, indent))
 }
 
+/// demo input: 2024-01-01T00:00+01:00
+fn date_time_code(value: &str) -> String {
+    let class = match (value.contains('T'), value.contains(':')) {
+        _ if value.ends_with(']') => "ZonedDateTime",
+        (true, _) if value.rfind(['Z', '+', '-']) > value.find('T') => "OffsetDateTime",
+        (true, _) => "LocalDateTime",
+        (false, true) => match split_zone(value) {
+            (_, "") => "LocalTime",
+            (time, offset) => {
+                return format!(
+                    "OffsetTime.of(LocalTime.parse(\"{time}\"), ZoneOffset.of(\"{offset}\"))"
+                )
+            }
+        },
+        (false, false) => "LocalDate",
+    };
+    format!("{class}.parse(\"{value}\")")
+}
+
 fn map_code(map: Vec<ValueKind<'_>>, indent: usize) -> String {
     let values = map
         .into_iter()
@@ -49,6 +93,17 @@ fn map_code(map: Vec<ValueKind<'_>>, indent: usize) -> String {
     format!("Map.of(\n{values}\n)")
 }
 
+fn tuple_code(tuple: Vec<ValueKind<'_>>, indent: usize) -> String {
+    let name = match tuple.len() {
+        2 => "Pair",
+        3 => "Triple",
+        _ => "Tuple",
+    };
+    let values = tuple.into_iter().map(|v| value_code(v, indent)).join(", ");
+
+    format!("new {name}<>({values})")
+}
+
 fn array_code(array: Vec<ValueKind<'_>>, indent: usize) -> String {
     let values = array
         .into_iter()
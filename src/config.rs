@@ -0,0 +1,196 @@
+use std::time::Duration;
+
+use crate::{DiffOptions, Tolerance};
+
+/// Applies the settings of a config file to the options.
+/// Lists are separated by commas and `#` starts a comment.
+/// demo input:
+/// # volatile fields
+/// ignore = createdAt, User.id
+/// unordered = true
+/// identity = id, key
+/// tolerance = 0.001, createdAt=5ms
+/// granularity = word
+pub fn read_config(text: &str, options: &mut DiffOptions) -> Result<(), String> {
+    for (number, line) in text.lines().enumerate() {
+        let line = line.split_once('#').map_or(line, |(line, _)| line).trim();
+        if line.is_empty() {
+            continue;
+        }
+        let error = |message: String| format!("line {}: {message}", number + 1);
+        let Some((key, value)) = line.split_once('=') else {
+            return Err(error(format!("expected key = value but got \"{line}\"")));
+        };
+        let value = value.trim();
+        match key.trim() {
+            "ignore" => options.ignore.extend(list(value)),
+            "identity" => options.identity_fields = list(value),
+            "tolerance" => {
+                for tolerance in list(value) {
+                    options
+                        .tolerances
+                        .push(parse_tolerance(&tolerance).map_err(error)?);
+                }
+            }
+            "unordered" => {
+                options.unordered_lists = value
+                    .parse()
+                    .map_err(|_| error(format!("expected true or false but got \"{value}\"")))?
+            }
+            "granularity" => options.granularity = value.parse().map_err(error)?,
+            key => return Err(error(format!("unknown setting \"{key}\""))),
+        }
+    }
+    Ok(())
+}
+
+/// A tolerance for every value or for a path in front of `=`.
+/// Numbers are absolute, `%` relative and units like `ms` durations
+/// demo input: price=0.5%
+pub fn parse_tolerance(text: &str) -> Result<(String, Tolerance), String> {
+    let (rule, value) = text.rsplit_once('=').unwrap_or(("**", text));
+    let (rule, value) = (rule.trim(), value.trim());
+    let invalid = || format!("expected a number, percentage or duration but got \"{value}\"");
+    let number = |n: &str| {
+        n.trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|n| n.is_finite() && *n >= 0.0)
+            .ok_or_else(invalid)
+    };
+    let tolerance = if let Some(percent) = value.strip_suffix('%') {
+        Tolerance::Relative(number(percent)? / 100.0)
+    } else if let Some((amount, unit)) = [
+        ("ns", 1e-9),
+        ("us", 1e-6),
+        ("ms", 1e-3),
+        ("s", 1.0),
+        ("m", 60.0),
+        ("h", 3600.0),
+        ("d", 86400.0),
+    ]
+    .into_iter()
+    .find_map(|(unit, seconds)| Some((value.strip_suffix(unit)?, seconds)))
+    {
+        let seconds = number(amount)? * unit;
+        let duration = Duration::try_from_secs_f64(seconds)
+            .map_err(|_| format!("the duration \"{value}\" is too long"))?;
+        Tolerance::Duration(duration)
+    } else {
+        Tolerance::Absolute(number(value)?)
+    };
+    Ok((rule.to_string(), tolerance))
+}
+
+/// demo input: createdAt=5 ms, display name
+fn list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        config::{parse_tolerance, read_config},
+        DiffOptions, Granularity, Tolerance,
+    };
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn config() {
+        let mut options = DiffOptions::default();
+        let out = read_config(
+            "# volatile fields\nignore = createdAt, **.meta.updatedAt\n\nunordered = true # sets\nidentity = key, id\ntolerance = 1%, createdAt = 5 ms\ngranularity = word\n",
+            &mut options,
+        );
+        assert_eq!(out, Ok(()));
+        assert_eq!(options.ignore, vec!["createdAt", "**.meta.updatedAt"]);
+        assert_eq!(options.identity_fields, vec!["key", "id"]);
+        assert!(options.unordered_lists);
+        assert_eq!(
+            options.tolerances,
+            vec![
+                ("**".to_string(), Tolerance::Relative(0.01)),
+                (
+                    "createdAt".to_string(),
+                    Tolerance::Duration(Duration::from_millis(5))
+                )
+            ]
+        );
+        assert_eq!(options.granularity, Granularity::Word);
+
+        let mut options = DiffOptions::default();
+        let out = read_config(
+            "ignore = display name, id
+",
+            &mut options,
+        );
+        assert_eq!(out, Ok(()));
+        assert_eq!(options.ignore, vec!["display name", "id"]);
+    }
+
+    #[test]
+    fn tolerances() {
+        assert_eq!(
+            parse_tolerance("0.001"),
+            Ok(("**".to_string(), Tolerance::Absolute(0.001)))
+        );
+        assert_eq!(
+            parse_tolerance("Order.price=50%"),
+            Ok(("Order.price".to_string(), Tolerance::Relative(0.5)))
+        );
+        assert_eq!(
+            parse_tolerance("createdAt=5ms"),
+            Ok((
+                "createdAt".to_string(),
+                Tolerance::Duration(Duration::from_millis(5))
+            ))
+        );
+        assert_eq!(
+            parse_tolerance("2m"),
+            Ok((
+                "**".to_string(),
+                Tolerance::Duration(Duration::from_secs(120))
+            ))
+        );
+        assert_eq!(
+            parse_tolerance("price=-1"),
+            Err("expected a number, percentage or duration but got \"-1\"".to_string())
+        );
+        assert_eq!(
+            parse_tolerance("ts=infs"),
+            Err("expected a number, percentage or duration but got \"infs\"".to_string())
+        );
+        assert_eq!(
+            parse_tolerance("NaN"),
+            Err("expected a number, percentage or duration but got \"NaN\"".to_string())
+        );
+        assert_eq!(
+            parse_tolerance("ts=1e30d"),
+            Err("the duration \"1e30d\" is too long".to_string())
+        );
+    }
+
+    #[test]
+    fn config_errors() {
+        let mut options = DiffOptions::default();
+        assert_eq!(
+            read_config("ignore = a\nordered = true", &mut options),
+            Err("line 2: unknown setting \"ordered\"".to_string())
+        );
+        assert_eq!(
+            read_config("unordered = yes", &mut options),
+            Err("line 1: expected true or false but got \"yes\"".to_string())
+        );
+        assert_eq!(
+            read_config("ignore", &mut options),
+            Err("line 1: expected key = value but got \"ignore\"".to_string())
+        );
+    }
+}
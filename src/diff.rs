@@ -1,11 +1,15 @@
-use itertools::Itertools;
+use std::{borrow::Cow, cell::Cell, str::FromStr, time::Duration};
 
-use crate::parser::{Dto, DtoField, ValueKind};
+use itertools::{EitherOrBoth, Itertools};
+use unicode_normalization::UnicodeNormalization;
+
+use crate::parser::{offset_seconds, split_zone, Dto, DtoField, ValueKind};
 
 #[derive(Debug, PartialEq)]
 pub enum Difference<'a> {
     Equal,
-    Type(&'a str, &'a str),
+    /// Values of different types: (expected, real)
+    Type((ValueKind<'a>, ValueKind<'a>)),
     Child(Vec<Difference<'a>>),
     ArrayChange(Vec<Difference<'a>>),
     DtoChange((&'a str, Vec<Difference<'a>>)),
@@ -15,76 +19,301 @@ pub enum Difference<'a> {
     UndefinedLeft(Option<ValueKind<'a>>),
     UndefinedRight(Option<ValueKind<'a>>),
     ClassChange(Box<Difference<'a>>),
-    FieldNameChange((&'a str, Box<Difference<'a>>)),
-    FieldValueChange((&'a str, Box<Difference<'a>>)),
+    FieldNameChange((Cow<'a, str>, Box<Difference<'a>>)),
+    FieldValueChange((Cow<'a, str>, Box<Difference<'a>>)),
+    /// The fields that Lombok callSuper printed as super=Parent(..)
+    ParentChange(Box<Difference<'a>>),
+    /// Real number and how far it is off: (real, delta)
+    NumberChange((Cow<'a, str>, String)),
+    /// Same number or time written with more or less digits: (expected, real)
+    PrecisionChange((Cow<'a, str>, Cow<'a, str>)),
+    /// Same local date and time in another offset or zone: (expected, real)
+    TimezoneChange((Cow<'a, str>, Cow<'a, str>)),
+    /// Same value written differently like true and TRUE: (expected, real)
+    FormatChange((Cow<'a, str>, Cow<'a, str>)),
+    /// Strings that look alike: (expected, real, what differs)
+    SubtleChange((String, String, Subtlety)),
+    /// Field of the expected Dto that the real one does not have
+    MissingField(DtoField<'a>),
+    /// Field of the real Dto that is not expected
+    ExtraField(DtoField<'a>),
+    /// Value of the real list at an index that is not expected
+    Inserted((usize, ValueKind<'a>)),
+    /// Value of the expected list at an index that is not in the real list
+    Removed((usize, ValueKind<'a>)),
+    /// Index of a value in the expected and the real list
+    Moved((usize, usize)),
+    /// Index of a value in the expected list and of the different value in the real list.
+    /// Values that were paired by their identity field are labeled with it: User(id=2)
+    Changed((usize, usize, Option<String>, Box<Difference<'a>>)),
+}
+
+/// Why two different strings look the same
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Subtlety {
+    /// Spaces, tabs, line endings or invisible characters like a non-breaking space
+    Whitespace,
+    Case,
+    /// The same characters composed of other code points
+    Unicode,
+}
+
+/// Settings of the comparison
+#[derive(Debug, Clone)]
+pub struct DiffOptions {
+    /// Compare lists by their content like sets. Maps are always compared by their keys
+    pub unordered_lists: bool,
+    /// Fields that identify a Dto inside of a list. The first one a Dto has is used
+    pub identity_fields: Vec<String>,
+    /// Fields that are always equal like timestamps or generated ids.
+    /// A field name or a path of class and field names: createdAt, User.id, Order.*.id, **.meta.createdAt
+    pub ignore: Vec<String>,
+    /// How far numbers and times may be off at a path. The path is a rule like in [DiffOptions::ignore],
+    /// ** applies it everywhere
+    pub tolerances: Vec<(String, Tolerance)>,
+    /// How finely strings are compared
+    pub granularity: Granularity,
+}
+
+/// The pieces that differing strings are split into
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Granularity {
+    /// Words for long or spaced strings, characters for short ones
+    /// and the whole value when they are mostly different
+    #[default]
+    Auto,
+    Char,
+    /// Words, spaces and single punctuation characters
+    Word,
+    Whole,
+}
+
+impl FromStr for Granularity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(Self::Auto),
+            "char" => Ok(Self::Char),
+            "word" => Ok(Self::Word),
+            "whole" => Ok(Self::Whole),
+            s => Err(format!(
+                "expected auto, char, word or whole but got \"{s}\""
+            )),
+        }
+    }
+}
+
+/// How far a value may be off and still be equal
+#[derive(Debug, Clone, PartialEq)]
+pub enum Tolerance {
+    /// Numbers that differ by at most this much: 0.001
+    Absolute(f64),
+    /// Numbers that differ by at most this fraction of the larger one: 1%
+    Relative(f64),
+    /// Times that are at most this far apart: 5ms
+    Duration(Duration),
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        Self {
+            unordered_lists: false,
+            identity_fields: vec!["id".to_string()],
+            ignore: vec![],
+            tolerances: vec![],
+            granularity: Granularity::Auto,
+        }
+    }
+}
+
+/// Where the comparison is inside of the values and what it has ignored so far
+#[derive(Clone)]
+struct Scope<'o> {
+    options: &'o DiffOptions,
+    path: Vec<Segment>,
+    ignored: &'o Cell<usize>,
+}
+
+#[derive(Clone)]
+enum Segment {
+    Class(String),
+    Field(String),
+}
+
+impl Scope<'_> {
+    fn class(&self, name: &str) -> Self {
+        let mut scope = self.clone();
+        scope
+            .path
+            .extend(name.split('.').map(|c| Segment::Class(c.to_string())));
+        scope
+    }
+
+    fn field(&self, name: &str) -> Self {
+        let mut scope = self.clone();
+        scope.path.push(Segment::Field(name.to_string()));
+        scope
+    }
+
+    /// Whether an ignore rule matches the field. Ignored fields are counted
+    fn ignores(&self, name: &str) -> bool {
+        let field = self.field(name);
+        let ignored = self.options.ignore.iter().any(|rule| field.matches(rule));
+        if ignored {
+            self.ignored.set(self.ignored.get() + 1);
+        }
+        ignored
+    }
+
+    fn matches(&self, rule: &str) -> bool {
+        let rule = rule.split('.').collect_vec();
+        matches_rule(&[&["**"], rule.as_slice()].concat(), &self.path)
+    }
+
+    fn tolerances(&self) -> impl Iterator<Item = &Tolerance> {
+        self.options
+            .tolerances
+            .iter()
+            .filter(|(rule, _)| self.matches(rule))
+            .map(|(_, tolerance)| tolerance)
+    }
 }
 
-pub fn diff<'a>(a: ValueKind<'a>, b: ValueKind<'a>) -> Difference<'a> {
+/// `**` matches any segments, `*` one field and a name a field or class of that name.
+/// Classes can be left out of a rule
+fn matches_rule(rule: &[&str], path: &[Segment]) -> bool {
+    match (rule.split_first(), path.split_first()) {
+        (None, None) => true,
+        (Some((&"**", rest)), _) => {
+            matches_rule(rest, path) || (!path.is_empty() && matches_rule(rule, &path[1..]))
+        }
+        (_, None) => false,
+        (None, Some(_)) => false,
+        (Some((&part, rest)), Some((Segment::Class(class), path_rest))) => {
+            (part == class && matches_rule(rest, path_rest)) || matches_rule(rule, path_rest)
+        }
+        (Some((&part, rest)), Some((Segment::Field(field), path_rest))) => {
+            (part == "*" || part == field) && matches_rule(rest, path_rest)
+        }
+    }
+}
+
+/// Compares the values. Returns the difference and how many fields [DiffOptions::ignore] left out
+pub fn diff<'a>(
+    a: ValueKind<'a>,
+    b: ValueKind<'a>,
+    options: &DiffOptions,
+) -> (Difference<'a>, usize) {
+    let ignored = Cell::new(0);
+    let scope = Scope {
+        options,
+        path: vec![],
+        ignored: &ignored,
+    };
+    let difference = diff_value(a, b, &scope);
+    (difference, ignored.get())
+}
+
+fn diff_value<'a>(a: ValueKind<'a>, b: ValueKind<'a>, scope: &Scope<'_>) -> Difference<'a> {
     match (a, b) {
         (ValueKind::Null, ValueKind::Null) => Difference::Equal,
-        (ValueKind::Null, ValueKind::String(_)) => Difference::Type("null", "String"),
-        (ValueKind::Null, ValueKind::Array(_)) => Difference::Type("null", "Array"),
-        (ValueKind::Null, ValueKind::Map(_)) => Difference::Type("null", "Map"),
-        (ValueKind::Null, ValueKind::Dto(dto)) => Difference::Type("null", dto.name),
-        (ValueKind::Null, ValueKind::Field(_)) => Difference::Type("null", "Field"),
-        (ValueKind::String(_), ValueKind::Null) => Difference::Type("String", ""),
-        (ValueKind::String(a), ValueKind::String(b)) => diff_string(a, b),
-        (ValueKind::String(_), ValueKind::Array(_)) => Difference::Type("String", "Array"),
-        (ValueKind::String(_), ValueKind::Map(_)) => Difference::Type("String", "Map"),
-        (ValueKind::String(_), ValueKind::Dto(dto)) => Difference::Type("String", dto.name),
-        (ValueKind::String(_), ValueKind::Field(_)) => Difference::Type("String", "Field"),
-        (ValueKind::Array(_), ValueKind::Null) => Difference::Type("Array", "null"),
-        (ValueKind::Array(_), ValueKind::String(_)) => Difference::Type("Array", "String"),
-        (ValueKind::Array(a), ValueKind::Array(b)) => diff_array(a, b),
-        (ValueKind::Array(_), ValueKind::Map(_)) => Difference::Type("Array", "Map"),
-        (ValueKind::Array(_), ValueKind::Dto(dto)) => Difference::Type("Array", dto.name),
-        (ValueKind::Array(_), ValueKind::Field(_)) => Difference::Type("Array", "Field"),
-        (ValueKind::Map(_), ValueKind::Null) => Difference::Type("Map", "null"),
-        (ValueKind::Map(_), ValueKind::String(_)) => Difference::Type("Map", "String"),
-        (ValueKind::Map(_), ValueKind::Array(_)) => Difference::Type("Map", "Array"),
-        (ValueKind::Map(a), ValueKind::Map(b)) => diff_array(a, b),
-        (ValueKind::Map(_), ValueKind::Dto(dto)) => Difference::Type("Map", dto.name),
-        (ValueKind::Map(_), ValueKind::Field(_)) => Difference::Type("Map", "Field"),
-        (ValueKind::Dto(dto), ValueKind::Null) => Difference::Type(dto.name, "null"),
-        (ValueKind::Dto(dto), ValueKind::String(_)) => Difference::Type(dto.name, "String"),
-        (ValueKind::Dto(dto), ValueKind::Array(_)) => Difference::Type(dto.name, "Array"),
-        (ValueKind::Dto(dto), ValueKind::Map(_)) => Difference::Type(dto.name, "Map"),
-        (ValueKind::Dto(a), ValueKind::Dto(b)) => diff_dto(a, b),
-        (ValueKind::Dto(dto), ValueKind::Field(_)) => Difference::Type(dto.name, "Field"),
-        (ValueKind::Field(_), ValueKind::Null) => Difference::Type("Field", "null"),
-        (ValueKind::Field(_), ValueKind::String(_)) => Difference::Type("Field", "String"),
-        (ValueKind::Field(_), ValueKind::Array(_)) => Difference::Type("Field", "Array"),
-        (ValueKind::Field(_), ValueKind::Map(_)) => Difference::Type("Field", "Map"),
-        (ValueKind::Field(_), ValueKind::Dto(dto)) => Difference::Type("Field", dto.name),
-        (ValueKind::Field(a), ValueKind::Field(b)) => diff_field(a, b),
-    }
-}
-
-fn diff_fields<'a>(a: Vec<DtoField<'a>>, b: Vec<DtoField<'a>>) -> Difference<'a> {
+        (
+            ValueKind::Integer(a) | ValueKind::Decimal(a),
+            ValueKind::Integer(b) | ValueKind::Decimal(b),
+        ) => diff_number(a, b, scope),
+        (ValueKind::Boolean(a), ValueKind::Boolean(b)) => diff_boolean(a, b),
+        (ValueKind::DateTime(a), ValueKind::DateTime(b)) => diff_date_time(a, b, scope),
+        (ValueKind::Array(a), ValueKind::Array(b)) if scope.options.unordered_lists => {
+            diff_unordered(a, b, scope)
+        }
+        (ValueKind::Array(a), ValueKind::Array(b)) => diff_array(a, b, scope),
+        (ValueKind::Map(a), ValueKind::Map(b)) => diff_map(a, b, scope),
+        (ValueKind::Tuple(a), ValueKind::Tuple(b)) => diff_tuple(a, b, scope),
+        (ValueKind::Dto(a), ValueKind::Dto(b)) => diff_dto(a, b, scope),
+        (ValueKind::Field(a), ValueKind::Field(b)) => diff_field(a, b, scope),
+        // "1" and 1 have the same text but are not the same type
+        (a, b) => match (a.scalar_text(), b.scalar_text()) {
+            (Some(text_a), Some(text_b)) if is_text(&a) && is_text(&b) => {
+                diff_text(text_a, text_b, scope)
+            }
+            _ => Difference::Type((a, b)),
+        },
+    }
+}
+
+/// Unquoted and quoted text. Constants are unquoted text that looks like an enum
+fn is_text(value: &ValueKind<'_>) -> bool {
+    matches!(
+        value,
+        ValueKind::String(_) | ValueKind::Quoted(_) | ValueKind::Constant(_)
+    )
+}
+
+pub fn type_name<'a>(value: &ValueKind<'a>) -> &'a str {
+    match value {
+        ValueKind::Null => "null",
+        ValueKind::String(_) | ValueKind::Quoted(_) => "String",
+        ValueKind::Integer(_) => "Integer",
+        ValueKind::Decimal(_) => "Decimal",
+        ValueKind::Boolean(_) => "Boolean",
+        ValueKind::Constant(_) => "Constant",
+        ValueKind::Uuid(_) => "UUID",
+        ValueKind::DateTime(_) => "DateTime",
+        ValueKind::Array(_) => "Array",
+        ValueKind::Map(_) => "Map",
+        ValueKind::Tuple(_) => "Tuple",
+        ValueKind::Dto(dto) => dto.name,
+        ValueKind::Field(_) => "Field",
+    }
+}
+
+/// Fields are compared with the field of the same name wherever it is
+fn diff_fields<'a>(
+    a: Vec<DtoField<'a>>,
+    b: Vec<DtoField<'a>>,
+    scope: &Scope<'_>,
+) -> Difference<'a> {
     if a == b {
         return Difference::Equal;
     }
-    let o = a
-        .into_iter()
-        .zip_longest(b)
-        .map(|d| match d {
-            itertools::EitherOrBoth::Both(a, b) => diff_field(Box::new(a), Box::new(b)),
-            _ => Difference::Equal,
-        })
-        .collect_vec();
+    let mut b = b.into_iter().map(Some).collect_vec();
+    let mut o = vec![];
+    for field in a {
+        let same_name = b
+            .iter()
+            .position(|f| f.as_ref().is_some_and(|f| f.name == field.name));
+        match same_name.and_then(|i| b[i].take()) {
+            Some(other) => o.push(diff_field(Box::new(field), Box::new(other), scope)),
+            None if scope.ignores(&field.name) => {}
+            None => o.push(Difference::MissingField(field)),
+        }
+    }
+    o.extend(
+        b.into_iter()
+            .flatten()
+            .filter(|f| !scope.ignores(&f.name))
+            .map(Difference::ExtraField),
+    );
+    if o.iter().all(|d| *d == Difference::Equal) {
+        return Difference::Equal;
+    }
     Difference::Child(o)
 }
-fn diff_field<'a>(a: Box<DtoField<'a>>, b: Box<DtoField<'a>>) -> Difference<'a> {
+fn diff_field<'a>(a: Box<DtoField<'a>>, b: Box<DtoField<'a>>, scope: &Scope<'_>) -> Difference<'a> {
     if a == b {
         return Difference::Equal;
     }
 
-    let name_diff = diff_string(a.name, b.name);
+    let name_diff = diff_string(&a.name, &b.name);
     if name_diff != Difference::Equal {
         return Difference::FieldNameChange((a.name, Box::new(name_diff)));
     }
+    if scope.ignores(&a.name) {
+        return Difference::Equal;
+    }
 
-    let value_diff = diff(a.value, b.value);
+    let value_diff = diff_value(a.value, b.value, &scope.field(&a.name));
     if value_diff != Difference::Equal {
         return Difference::FieldValueChange((a.name, Box::new(value_diff)));
     }
@@ -92,7 +321,7 @@ fn diff_field<'a>(a: Box<DtoField<'a>>, b: Box<DtoField<'a>>) -> Difference<'a>
     Difference::Equal
 }
 
-fn diff_dto<'a>(a: Dto<'a>, b: Dto<'a>) -> Difference<'a> {
+fn diff_dto<'a>(a: Dto<'a>, b: Dto<'a>, scope: &Scope<'_>) -> Difference<'a> {
     if a == b {
         return Difference::Equal;
     }
@@ -100,31 +329,667 @@ fn diff_dto<'a>(a: Dto<'a>, b: Dto<'a>) -> Difference<'a> {
     if a.name != b.name {
         return Difference::ClassChange(Box::new(diff_string(a.name, b.name)));
     }
+    let scope = &scope.class(a.name);
 
-    let fields_change = diff_fields(a.fields, b.fields);
-    if let Difference::Child(o) = fields_change {
-        return Difference::DtoChange((a.name, o));
+    let parent_change = match (a.parent, b.parent) {
+        (Some(a), Some(b)) => diff_value(*a, *b, scope),
+        (Some(a), None) => Difference::UndefinedRight(Some(*a)),
+        (None, Some(b)) => Difference::UndefinedLeft(Some(*b)),
+        (None, None) => Difference::Equal,
+    };
+    let fields_change = diff_fields(a.fields, b.fields, scope);
+    match (parent_change, fields_change) {
+        (Difference::Equal, Difference::Child(o)) => Difference::DtoChange((a.name, o)),
+        (Difference::Equal, fields_change) => fields_change,
+        (parent_change, fields_change) => {
+            let mut o = vec![Difference::ParentChange(Box::new(parent_change))];
+            if let Difference::Child(fields) = fields_change {
+                o.extend(fields);
+            }
+            Difference::DtoChange((a.name, o))
+        }
     }
-    fields_change
 }
 
-fn diff_array<'a>(a: Vec<ValueKind<'a>>, b: Vec<ValueKind<'a>>) -> Difference<'a> {
+/// Aligns the lists by their longest common subsequence.
+/// Equal values that are not aligned were moved. The rest between two aligned values was changed,
+/// removed or inserted
+fn diff_array<'a>(
+    a: Vec<ValueKind<'a>>,
+    b: Vec<ValueKind<'a>>,
+    scope: &Scope<'_>,
+) -> Difference<'a> {
     if a == b {
         return Difference::Equal;
     }
+    let common = longest_common_subsequence(&a, &b);
+    let mut a = a.into_iter().map(Some).collect_vec();
+    let mut b = b.into_iter().map(Some).collect_vec();
+    for &(i, j) in &common {
+        a[i] = None;
+        b[j] = None;
+    }
+
+    let mut o = vec![];
+    for (i, value) in a.iter_mut().enumerate() {
+        let Some(j) = b
+            .iter()
+            .position(|v| v.is_some() && v.as_ref() == value.as_ref())
+        else {
+            continue;
+        };
+        *value = None;
+        b[j] = None;
+        o.push(Difference::Moved((i, j)));
+    }
+
+    // The gap is the number of aligned values in front of a value
+    let mut removed = vec![];
+    let mut inserted = vec![];
+    let (mut a_start, mut b_start) = (0, 0);
+    for (gap, (a_end, b_end)) in common.into_iter().chain([(a.len(), b.len())]).enumerate() {
+        removed.extend((a_start..a_end).filter_map(|i| Some((gap, (i, a[i].take()?)))));
+        inserted.extend((b_start..b_end).filter_map(|j| Some((gap, (j, b[j].take()?)))));
+        (a_start, b_start) = (a_end + 1, b_end + 1);
+    }
+    pair_changes(removed, inserted, scope, &mut o);
+    array_change(o)
+}
+
+/// Indices of the values that are in both lists in the same order
+pub fn longest_common_subsequence<T: PartialEq>(a: &[T], b: &[T]) -> Vec<(usize, usize)> {
+    longest_common_subsequence_by(a, b, |a, b| a == b)
+}
+
+/// Like [longest_common_subsequence] with values that are the same when `same` says so
+pub fn longest_common_subsequence_by<T>(
+    a: &[T],
+    b: &[T],
+    same: impl Fn(&T, &T) -> bool,
+) -> Vec<(usize, usize)> {
+    let mut lengths = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lengths[i][j] = match same(&a[i], &b[j]) {
+                true => lengths[i + 1][j + 1] + 1,
+                false => lengths[i + 1][j].max(lengths[i][j + 1]),
+            };
+        }
+    }
+
+    let mut out = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if same(&a[i], &b[j]) {
+            out.push((i, j));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    out
+}
+
+/// Pairs the removed and inserted values that belong together and compares them.
+/// Values with the same identity are paired first, then the most similar ones and then the rest
+/// of the same gap in order. What is left over was only removed or inserted
+fn pair_changes<'a>(
+    removed: Vec<(usize, (usize, ValueKind<'a>))>,
+    inserted: Vec<(usize, (usize, ValueKind<'a>))>,
+    scope: &Scope<'_>,
+    o: &mut Vec<Difference<'a>>,
+) {
+    let removed_ids = removed
+        .iter()
+        .map(|(_, (_, v))| identity(v, scope.options))
+        .collect_vec();
+    let inserted_ids = inserted
+        .iter()
+        .map(|(_, (_, v))| identity(v, scope.options))
+        .collect_vec();
+    let mut pairs = vec![];
+    let mut paired_removed = vec![false; removed.len()];
+    let mut paired_inserted = vec![false; inserted.len()];
+
+    for (i, id) in removed_ids.iter().enumerate() {
+        let Some(id) = id else {
+            continue;
+        };
+        let same = (0..inserted.len())
+            .find(|&j| !paired_inserted[j] && inserted_ids[j].as_ref() == Some(id));
+        if let Some(j) = same {
+            paired_removed[i] = true;
+            paired_inserted[j] = true;
+            pairs.push((i, j, Some(id.clone())));
+        }
+    }
+
+    // Values that both have another identity are never the same value
+    let comparable = |i: usize, j: usize| removed_ids[i].is_none() || inserted_ids[j].is_none();
+    let mut candidates = vec![];
+    for (i, (_, (_, a))) in removed.iter().enumerate() {
+        for (j, (_, (_, b))) in inserted.iter().enumerate() {
+            let score = similarity(a, b);
+            if score > 0.0 && comparable(i, j) {
+                candidates.push((score, i, j));
+            }
+        }
+    }
+    candidates.sort_by(|a, b| b.0.total_cmp(&a.0));
+    for (_, i, j) in candidates {
+        if !paired_removed[i] && !paired_inserted[j] {
+            paired_removed[i] = true;
+            paired_inserted[j] = true;
+            pairs.push((i, j, None));
+        }
+    }
+
+    let gaps = removed
+        .iter()
+        .chain(&inserted)
+        .map(|(gap, _)| *gap)
+        .sorted()
+        .dedup()
+        .collect_vec();
+    let mut leftover_removed = vec![];
+    let mut leftover_inserted = vec![];
+    for gap in gaps {
+        let unpaired_removed =
+            (0..removed.len()).filter(|&i| !paired_removed[i] && removed[i].0 == gap);
+        let unpaired_inserted =
+            (0..inserted.len()).filter(|&j| !paired_inserted[j] && inserted[j].0 == gap);
+        for pair in unpaired_removed.zip_longest(unpaired_inserted) {
+            match pair {
+                EitherOrBoth::Both(i, j) if comparable(i, j) => pairs.push((i, j, None)),
+                EitherOrBoth::Both(i, j) => {
+                    leftover_removed.push(i);
+                    leftover_inserted.push(j);
+                }
+                EitherOrBoth::Left(i) => leftover_removed.push(i),
+                EitherOrBoth::Right(j) => leftover_inserted.push(j),
+            }
+        }
+    }
+
+    pairs.sort_by_key(|p| p.0);
+    leftover_removed.sort();
+    leftover_inserted.sort();
+    let mut removed = removed.into_iter().map(Some).collect_vec();
+    let mut inserted = inserted.into_iter().map(Some).collect_vec();
+    let mut changes = vec![];
+    for (i, j, id) in pairs {
+        let (Some((gap, (a_index, a))), Some((_, (b_index, b)))) =
+            (removed[i].take(), inserted[j].take())
+        else {
+            continue;
+        };
+        match diff_value(a, b, scope) {
+            Difference::Equal => {}
+            d => changes.push((
+                gap,
+                Difference::Changed((a_index, b_index, id, Box::new(d))),
+            )),
+        }
+    }
+    for i in leftover_removed {
+        if let Some((gap, removed)) = removed[i].take() {
+            changes.push((gap, Difference::Removed(removed)));
+        }
+    }
+    for j in leftover_inserted {
+        if let Some((gap, inserted)) = inserted[j].take() {
+            changes.push((gap, Difference::Inserted(inserted)));
+        }
+    }
+    // Stable, so the changes of a gap stay in front of what was removed and inserted
+    changes.sort_by_key(|(gap, _)| *gap);
+    o.extend(changes.into_iter().map(|(_, d)| d));
+}
+
+/// The first identity field of a Dto with its value: User(id=2)
+fn identity(value: &ValueKind<'_>, options: &DiffOptions) -> Option<String> {
+    let ValueKind::Dto(dto) = value else {
+        return None;
+    };
+    options.identity_fields.iter().find_map(|name| {
+        let field = dto.fields.iter().find(|f| f.name == name.as_str())?;
+        Some(format!(
+            "{}({}={})",
+            dto.name,
+            field.name,
+            field.value.scalar_text()?
+        ))
+    })
+}
+
+/// How much two values have in common from 0 to 1
+fn similarity(a: &ValueKind<'_>, b: &ValueKind<'_>) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+    match (a, b) {
+        (ValueKind::Dto(a), ValueKind::Dto(b)) if a.name == b.name => {
+            let same: f64 = a
+                .fields
+                .iter()
+                .filter_map(|f| {
+                    let other = b.fields.iter().find(|o| o.name == f.name)?;
+                    Some(similarity(&f.value, &other.value))
+                })
+                .sum();
+            same / a.fields.len().max(b.fields.len()).max(1) as f64
+        }
+        (ValueKind::Field(a), ValueKind::Field(b)) if a.name == b.name => {
+            similarity(&a.value, &b.value)
+        }
+        (ValueKind::Array(a), ValueKind::Array(b))
+        | (ValueKind::Map(a), ValueKind::Map(b))
+        | (ValueKind::Tuple(a), ValueKind::Tuple(b)) => {
+            let same = a.iter().filter(|v| b.contains(v)).count();
+            2.0 * same as f64 / (a.len() + b.len()).max(1) as f64
+        }
+        _ => 0.0,
+    }
+}
+
+fn array_change(o: Vec<Difference<'_>>) -> Difference<'_> {
+    match o.is_empty() {
+        true => Difference::Equal,
+        false => Difference::ArrayChange(o),
+    }
+}
+
+/// The values of tuples belong to their position
+fn diff_tuple<'a>(
+    a: Vec<ValueKind<'a>>,
+    b: Vec<ValueKind<'a>>,
+    scope: &Scope<'_>,
+) -> Difference<'a> {
     let o = a
         .into_iter()
         .zip_longest(b)
-        .map(|d| match d {
-            itertools::EitherOrBoth::Both(a, b) => diff(a, b),
-            itertools::EitherOrBoth::Left(d) => Difference::UndefinedRight(Some(d)),
-            itertools::EitherOrBoth::Right(d) => Difference::UndefinedLeft(Some(d)),
+        .enumerate()
+        .filter_map(|(i, pair)| match pair {
+            EitherOrBoth::Both(a, b) => match diff_value(a, b, scope) {
+                Difference::Equal => None,
+                d => Some(Difference::Changed((i, i, None, Box::new(d)))),
+            },
+            EitherOrBoth::Left(a) => Some(Difference::Removed((i, a))),
+            EitherOrBoth::Right(b) => Some(Difference::Inserted((i, b))),
         })
         .collect_vec();
-    Difference::ArrayChange(o)
+    array_change(o)
+}
+
+/// Values that are in both lists are equal wherever they are.
+/// The rest is compared in order
+fn diff_unordered<'a>(
+    a: Vec<ValueKind<'a>>,
+    b: Vec<ValueKind<'a>>,
+    scope: &Scope<'_>,
+) -> Difference<'a> {
+    let mut b = b.into_iter().map(Some).collect_vec();
+    let mut removed = vec![];
+    for (i, value) in a.into_iter().enumerate() {
+        match b.iter().position(|v| v.as_ref() == Some(&value)) {
+            Some(j) => b[j] = None,
+            None => removed.push((i, value)),
+        }
+    }
+    let removed = removed.into_iter().map(|r| (0, r)).collect_vec();
+    let inserted = b
+        .into_iter()
+        .enumerate()
+        .filter_map(|(j, v)| Some((0, (j, v?))))
+        .collect_vec();
+    let mut o = vec![];
+    pair_changes(removed, inserted, scope, &mut o);
+    array_change(o)
+}
+
+/// Entries are compared with the entry of the same key wherever it is
+fn diff_map<'a>(a: Vec<ValueKind<'a>>, b: Vec<ValueKind<'a>>, scope: &Scope<'_>) -> Difference<'a> {
+    let mut b = b.into_iter().map(Some).collect_vec();
+    let mut o = vec![];
+    for entry in a {
+        let same_key = b
+            .iter()
+            .position(|e| e.as_ref().is_some_and(|e| is_same_key(&entry, e)));
+        match same_key.and_then(|i| b[i].take()) {
+            Some(other) => o.push(diff_value(entry, other, scope)),
+            None if is_ignored_entry(&entry, scope) => {}
+            None => o.push(Difference::UndefinedRight(Some(entry))),
+        }
+    }
+    o.extend(
+        b.into_iter()
+            .flatten()
+            .filter(|e| !is_ignored_entry(e, scope))
+            .map(|e| Difference::UndefinedLeft(Some(e))),
+    );
+    o.retain(|d| *d != Difference::Equal);
+    array_change(o)
+}
+
+fn is_ignored_entry(entry: &ValueKind<'_>, scope: &Scope<'_>) -> bool {
+    match entry {
+        ValueKind::Field(field) => scope.ignores(&field.name),
+        _ => false,
+    }
 }
 
-fn diff_string<'a>(a: &'a str, b: &'a str) -> Difference<'a> {
+fn is_same_key(a: &ValueKind<'_>, b: &ValueKind<'_>) -> bool {
+    match (a, b) {
+        (ValueKind::Field(a), ValueKind::Field(b)) => a.name == b.name,
+        (a, b) => a == b,
+    }
+}
+
+fn diff_number<'a>(a: Cow<'a, str>, b: Cow<'a, str>, scope: &Scope<'_>) -> Difference<'a> {
+    if a == b || within_number_tolerance(&a, &b, scope) {
+        return Difference::Equal;
+    }
+    match decimal_delta(&a, &b) {
+        Some(delta) if delta.trim_start_matches(['-', '0', '.']).is_empty() => {
+            Difference::PrecisionChange((a, b))
+        }
+        Some(delta) => Difference::NumberChange((b, delta)),
+        // Too long or with an exponent. Only the digits can tell if the values are the same
+        None if normalize(&a).is_some_and(|n| Some(n) == normalize(&b)) => {
+            Difference::PrecisionChange((a, b))
+        }
+        None => diff_string(&a, &b),
+    }
+}
+
+/// A number as its sign, its significant digits and the exponent of the last one
+/// demo input: -1.50E3
+fn normalize(text: &str) -> Option<(bool, String, i64)> {
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(text) => (true, text),
+        None => (false, text),
+    };
+    let (mantissa, exponent) = text.split_once(['e', 'E']).unwrap_or((text, "0"));
+    let (int, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let exponent = exponent.parse::<i64>().ok()? - fraction.len() as i64;
+    let digits = format!("{int}{fraction}");
+    let digits = digits.trim_start_matches('0');
+    if digits.is_empty() {
+        return Some((false, String::new(), 0));
+    }
+    let significant = digits.trim_end_matches('0');
+    let zeros = (digits.len() - significant.len()) as i64;
+    Some((negative, significant.to_string(), exponent + zeros))
+}
+
+fn within_number_tolerance(a: &str, b: &str, scope: &Scope<'_>) -> bool {
+    let (Ok(a), Ok(b)) = (a.parse::<f64>(), b.parse::<f64>()) else {
+        return false;
+    };
+    let delta = (b - a).abs();
+    scope.tolerances().any(|tolerance| match tolerance {
+        Tolerance::Absolute(epsilon) => delta <= *epsilon,
+        Tolerance::Relative(fraction) => delta <= fraction * a.abs().max(b.abs()),
+        Tolerance::Duration(_) => false,
+    })
+}
+
+/// Exact difference of two numbers without exponents
+/// demo input: "1.5", "1.75"
+fn decimal_delta(a: &str, b: &str) -> Option<String> {
+    let (a_digits, a_scale) = decimal(a)?;
+    let (b_digits, b_scale) = decimal(b)?;
+    let scale = a_scale.max(b_scale);
+    let a_digits = a_digits.checked_mul(10i128.checked_pow(scale - a_scale)?)?;
+    let b_digits = b_digits.checked_mul(10i128.checked_pow(scale - b_scale)?)?;
+    Some(format_decimal(b_digits.checked_sub(a_digits)?, scale))
+}
+
+/// Exact value of a number as its digits and the amount of them after the point
+/// demo input: -1.50
+fn decimal(text: &str) -> Option<(i128, u32)> {
+    let (int, fraction) = text.split_once('.').unwrap_or((text, ""));
+    let digits = format!("{int}{fraction}").parse().ok()?;
+    Some((digits, fraction.len() as u32))
+}
+
+fn format_decimal(digits: i128, scale: u32) -> String {
+    if scale == 0 {
+        return digits.to_string();
+    }
+    let sign = if digits < 0 { "-" } else { "" };
+    let digits = format!(
+        "{:0>width$}",
+        digits.unsigned_abs(),
+        width = scale as usize + 1
+    );
+    let (int, fraction) = digits.split_at(digits.len() - scale as usize);
+    let fraction = fraction.trim_end_matches('0');
+    match fraction {
+        "" => format!("{sign}{int}"),
+        _ => format!("{sign}{int}.{fraction}"),
+    }
+}
+
+fn diff_boolean<'a>(a: Cow<'a, str>, b: Cow<'a, str>) -> Difference<'a> {
+    if a == b {
+        return Difference::Equal;
+    }
+    if a.eq_ignore_ascii_case(&b) {
+        return Difference::FormatChange((a, b));
+    }
+    diff_string(&a, &b)
+}
+
+fn diff_date_time<'a>(a: Cow<'a, str>, b: Cow<'a, str>, scope: &Scope<'_>) -> Difference<'a> {
+    if a == b || within_time_tolerance(&a, &b, scope) {
+        return Difference::Equal;
+    }
+    match (instant(&a), instant(&b)) {
+        (Some(a_instant), Some(b_instant)) if a_instant == b_instant => {
+            match split_date_time(&a).1 == split_date_time(&b).1 {
+                true => Difference::PrecisionChange((a, b)),
+                false => Difference::TimezoneChange((a, b)),
+            }
+        }
+        _ => diff_string(&a, &b),
+    }
+}
+
+fn within_time_tolerance(a: &str, b: &str, scope: &Scope<'_>) -> bool {
+    let (Some(a), Some(b)) = (seconds(a), seconds(b)) else {
+        return false;
+    };
+    let delta = (b - a).abs();
+    scope.tolerances().any(|tolerance| match tolerance {
+        Tolerance::Duration(duration) => delta <= duration.as_secs_f64(),
+        _ => false,
+    })
+}
+
+/// Seconds since 1970 in UTC, or since midnight for a time without a date
+fn seconds(text: &str) -> Option<f64> {
+    let (whole, fraction) = instant(text)?;
+    Some(whole as f64 + format!("0.{fraction}").parse::<f64>().ok()?)
+}
+
+/// Exact point in time: whole seconds like [seconds] and the digits of the fraction of a second
+/// without trailing zeros. Named zones are not known so only the offset is used
+/// demo input: 2024-01-01T00:00:30.5+01:00[Europe/Berlin]
+fn instant(text: &str) -> Option<(i64, &str)> {
+    let (local, zone) = split_date_time(text);
+    let (date, time) = match local.split_once('T') {
+        Some((date, time)) => (Some(date), time),
+        None if local.contains(':') => (None, local),
+        None => (Some(local), ""),
+    };
+    let days = match date {
+        Some(date) => {
+            let mut parts = date.split('-').map(|p| p.parse::<i64>().ok());
+            days_from_civil(parts.next()??, parts.next()??, parts.next()??)
+        }
+        None => 0,
+    };
+    let mut time_seconds = 0;
+    let mut fraction = "";
+    for (part, unit) in time.split(':').filter(|p| !p.is_empty()).zip([3600, 60, 1]) {
+        let (whole, part_fraction) = part.split_once('.').unwrap_or((part, ""));
+        time_seconds += whole.parse::<i64>().ok()? * unit;
+        fraction = part_fraction.trim_end_matches('0');
+    }
+    let offset = match zone.split('[').next().unwrap_or("") {
+        "" | "Z" => 0,
+        offset => offset_seconds(offset)?,
+    };
+    Some((days * 86400 + time_seconds - offset, fraction))
+}
+
+/// Days since 1970-01-01 of a date of the proleptic Gregorian calendar
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// demo input: 2024-01-01T00:00+01:00
+fn split_date_time(text: &str) -> (&str, &str) {
+    let time_start = text.find(':').map_or(text.len(), |i| i.saturating_sub(2));
+    let (_, zone) = split_zone(&text[time_start..]);
+    text.split_at(text.len() - zone.len())
+}
+
+/// Strings that are longer are compared by their words
+const LONG_TEXT: usize = 40;
+
+/// Compares strings in the pieces of [DiffOptions::granularity]
+fn diff_text<'a>(a: &str, b: &str, scope: &Scope<'_>) -> Difference<'a> {
+    if a == b {
+        return Difference::Equal;
+    }
+    if let Some(subtlety) = subtlety(a, b) {
+        return Difference::SubtleChange((a.to_string(), b.to_string(), subtlety));
+    }
+    match scope.options.granularity {
+        Granularity::Char => diff_string(a, b),
+        Granularity::Word => diff_words(a, b),
+        Granularity::Whole => replace_text(a, b),
+        Granularity::Auto => {
+            let is_text =
+                |t: &str| t.contains(char::is_whitespace) || t.chars().count() > LONG_TEXT;
+            let diff = match is_text(a) || is_text(b) {
+                true => diff_words(a, b),
+                false => diff_string(a, b),
+            };
+            // Less than half of the strings is the same
+            let Difference::Child(chunks) = &diff else {
+                return diff;
+            };
+            let same: usize = chunks
+                .iter()
+                .map(|c| match c {
+                    Difference::CharsEqual(s) => s.chars().count(),
+                    _ => 0,
+                })
+                .sum();
+            match 4 * same < a.chars().count() + b.chars().count() {
+                true => replace_text(a, b),
+                false => diff,
+            }
+        }
+    }
+}
+
+fn subtlety(a: &str, b: &str) -> Option<Subtlety> {
+    let visible = |t: &str| t.chars().filter(|c| !is_invisible(*c)).collect::<String>();
+    if visible(a) == visible(b) {
+        return Some(Subtlety::Whitespace);
+    }
+    if a.to_lowercase() == b.to_lowercase() {
+        return Some(Subtlety::Case);
+    }
+    if a.nfkc().eq(b.nfkc()) {
+        return Some(Subtlety::Unicode);
+    }
+    None
+}
+
+/// Whitespace and characters that are not printed like a zero width space
+pub fn is_invisible(c: char) -> bool {
+    c.is_whitespace()
+        || matches!(
+            c,
+            '\u{200b}'..='\u{200f}' | '\u{2060}'..='\u{2064}' | '\u{feff}' | '\u{ad}'
+        )
+}
+
+fn replace_text<'a>(a: &str, b: &str) -> Difference<'a> {
+    Difference::Child(vec![
+        Difference::CharsRemove(a.to_string()),
+        Difference::CharsAdd(b.to_string()),
+    ])
+}
+
+/// Aligns the words of the strings by their longest common subsequence
+fn diff_words<'a>(a: &str, b: &str) -> Difference<'a> {
+    let a = words(a);
+    let b = words(b);
+    let mut o = vec![];
+    let (mut i, mut j) = (0, 0);
+    for (a_end, b_end) in longest_common_subsequence(&a, &b)
+        .into_iter()
+        .chain([(a.len(), b.len())])
+    {
+        push_chars(&mut o, Difference::CharsRemove(a[i..a_end].concat()));
+        push_chars(&mut o, Difference::CharsAdd(b[j..b_end].concat()));
+        if let Some(same) = a.get(a_end) {
+            push_chars(&mut o, Difference::CharsEqual(same.to_string()));
+        }
+        (i, j) = (a_end + 1, b_end + 1);
+    }
+    Difference::Child(o)
+}
+
+/// Splits into words, runs of whitespace and single other characters
+/// demo input: https://example.com/a b
+fn words(text: &str) -> Vec<&str> {
+    let mut out = vec![];
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        let kind = |c: char| (c.is_alphanumeric() || c == '_', c.is_whitespace());
+        let end = match kind(c) {
+            (false, false) => c.len_utf8(),
+            first => rest.find(|c| kind(c) != first).unwrap_or(rest.len()),
+        };
+        let (word, tail) = rest.split_at(end);
+        out.push(word);
+        rest = tail;
+    }
+    out
+}
+
+/// Appends to the last chunk of the same kind
+fn push_chars<'a>(o: &mut Vec<Difference<'a>>, chunk: Difference<'a>) {
+    match (o.last_mut(), chunk) {
+        (
+            _,
+            Difference::CharsEqual(text)
+            | Difference::CharsRemove(text)
+            | Difference::CharsAdd(text),
+        ) if text.is_empty() => {}
+        (Some(Difference::CharsEqual(last)), Difference::CharsEqual(text))
+        | (Some(Difference::CharsRemove(last)), Difference::CharsRemove(text))
+        | (Some(Difference::CharsAdd(last)), Difference::CharsAdd(text)) => last.push_str(&text),
+        (_, chunk) => o.push(chunk),
+    }
+}
+
+fn diff_string<'a>(a: &str, b: &str) -> Difference<'a> {
     if a == b {
         return Difference::Equal;
     }
@@ -145,12 +1010,115 @@ fn diff_string<'a>(a: &'a str, b: &'a str) -> Difference<'a> {
 mod tests {
 
     use crate::{
-        diff::{self, Difference},
+        diff::{self, DiffOptions, Difference},
+        parser::{scalar, ValueKind},
         test_data,
     };
     use pretty_assertions::assert_eq;
 
-    use super::diff_string;
+    use super::{diff_string, Granularity, Subtlety};
+
+    #[test]
+    fn granularity() {
+        let diff = |a: &'static str, b: &'static str, granularity| {
+            let options = DiffOptions {
+                granularity,
+                ..Default::default()
+            };
+            diff::diff(
+                ValueKind::String(a.into()),
+                ValueKind::String(b.into()),
+                &options,
+            )
+            .0
+        };
+        let chunks = |chunks: &[(&str, &str)]| {
+            Difference::Child(
+                chunks
+                    .iter()
+                    .map(|(kind, text)| match *kind {
+                        "=" => Difference::CharsEqual(text.to_string()),
+                        "-" => Difference::CharsRemove(text.to_string()),
+                        _ => Difference::CharsAdd(text.to_string()),
+                    })
+                    .collect(),
+            )
+        };
+
+        assert_eq!(
+            diff(
+                "the quick brown fox",
+                "the slow brown fox",
+                Granularity::Auto
+            ),
+            chunks(&[
+                ("=", "the "),
+                ("-", "quick"),
+                ("+", "slow"),
+                ("=", " brown fox")
+            ])
+        );
+        assert_eq!(
+            diff(
+                "https://example.com/users/1?page=2",
+                "https://example.com/users/12?page=3",
+                Granularity::Word
+            ),
+            chunks(&[
+                ("=", "https://example.com/users/"),
+                ("-", "1"),
+                ("+", "12"),
+                ("=", "?page="),
+                ("-", "2"),
+                ("+", "3"),
+            ])
+        );
+        assert_eq!(
+            diff("lastname", "lastnames", Granularity::Auto),
+            chunks(&[("=", "lastname"), ("+", "s")])
+        );
+        assert_eq!(
+            diff("first", "second", Granularity::Auto),
+            chunks(&[("-", "first"), ("+", "second")])
+        );
+        assert_eq!(
+            diff("first", "second", Granularity::Char),
+            diff_string("first", "second")
+        );
+        assert_eq!(
+            diff("lastname", "lastnames", Granularity::Whole),
+            chunks(&[("-", "lastname"), ("+", "lastnames")])
+        );
+    }
+
+    #[test]
+    fn subtle_strings() {
+        let diff = |a: &'static str, b: &'static str| {
+            diff::diff(
+                ValueKind::Quoted(a.into()),
+                ValueKind::Quoted(b.into()),
+                &DiffOptions::default(),
+            )
+            .0
+        };
+        let subtle = |a: &str, b: &str, subtlety| {
+            Difference::SubtleChange((a.to_string(), b.to_string(), subtlety))
+        };
+
+        assert_eq!(
+            diff("line\r\n", "line\n"),
+            subtle("line\r\n", "line\n", Subtlety::Whitespace)
+        );
+        assert_eq!(
+            diff("a", "a\u{200b}"),
+            subtle("a", "a\u{200b}", Subtlety::Whitespace)
+        );
+        assert_eq!(diff("Id", "ID"), subtle("Id", "ID", Subtlety::Case));
+        assert_eq!(
+            diff("\u{fb01}le", "file"),
+            subtle("\u{fb01}le", "file", Subtlety::Unicode)
+        );
+    }
 
     #[test]
     fn string_test() {
@@ -165,10 +1133,72 @@ mod tests {
         );
     }
 
+    #[test]
+    fn typed_scalars() {
+        let diff = |a: &'static str, b: &'static str| {
+            diff::diff(scalar(a.into()), scalar(b.into()), &DiffOptions::default()).0
+        };
+
+        assert_eq!(
+            diff("500", "501"),
+            Difference::NumberChange(("501".into(), "1".into()))
+        );
+        assert_eq!(
+            diff("1.25", "1.2"),
+            Difference::NumberChange(("1.2".into(), "-0.05".into()))
+        );
+        assert_eq!(
+            diff("500", "500.0"),
+            Difference::PrecisionChange(("500".into(), "500.0".into()))
+        );
+        let long = "1234567890123456789012345678901234567890";
+        assert_eq!(
+            diff(long, "1234567890123456789012345678901234567891"),
+            Difference::Child(vec![
+                Difference::CharsEqual(long[..39].to_string()),
+                Difference::CharsRemove("0".to_string()),
+                Difference::CharsAdd("1".to_string()),
+            ])
+        );
+        assert_eq!(
+            diff("1.5E3", "1500.0"),
+            Difference::PrecisionChange(("1.5E3".into(), "1500.0".into()))
+        );
+        assert_eq!(
+            diff("true", "TRUE"),
+            Difference::FormatChange(("true".into(), "TRUE".into()))
+        );
+        assert_eq!(
+            diff("2024-01-01T00:00", "2024-01-01T00:00:00Z"),
+            Difference::TimezoneChange(("2024-01-01T00:00".into(), "2024-01-01T00:00:00Z".into()))
+        );
+        assert_eq!(
+            diff("10:00+01:00", "09:00Z"),
+            Difference::TimezoneChange(("10:00+01:00".into(), "09:00Z".into()))
+        );
+        assert!(!matches!(
+            diff("10:00+01:00", "10:00+02:00"),
+            Difference::TimezoneChange(_) | Difference::PrecisionChange(_)
+        ));
+        assert!(!matches!(
+            diff("2024-01-01T00:00:00.000000001Z", "2024-01-01T00:00:00Z"),
+            Difference::PrecisionChange(_)
+        ));
+        assert_eq!(
+            diff("12:30", "12:30:00.000"),
+            Difference::PrecisionChange(("12:30".into(), "12:30:00.000".into()))
+        );
+    }
+
     #[test]
     fn complicated() {
         let complicated = test_data::get_complicated_expected();
-        let out = diff::diff(complicated.expected, complicated.real);
+        let out = diff::diff(
+            complicated.expected,
+            complicated.real,
+            &DiffOptions::default(),
+        )
+        .0;
 
         assert_eq!(
             out,
@@ -180,15 +1210,15 @@ mod tests {
                     Difference::Equal,
                     Difference::Equal,
                     Difference::FieldValueChange((
-                        "e",
+                        "e".into(),
                         Box::new(Difference::ArrayChange(vec![Difference::FieldValueChange(
                             (
-                                "eee",
+                                "eee".into(),
                                 Box::new(Difference::DtoChange((
                                     "Complicated",
                                     vec![
                                         Difference::FieldValueChange((
-                                            "a",
+                                            "a".into(),
                                             Box::new(Difference::Child(vec![
                                                 Difference::CharsRemove("a".to_string()),
                                                 Difference::CharsAdd("b".to_string())
@@ -0,0 +1,49 @@
+use std::str::FromStr;
+
+use crate::pretty::Notation;
+
+/// How a difference is written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    /// Everything on one line, which logana appends to the failure
+    #[default]
+    Line,
+    /// The line with the amount of changes of every Dto and list. Dtos and lists deeper than
+    /// `depth` and more than `changes` changes of one are left out
+    Summary { depth: usize, changes: usize },
+    /// One change per line with its path. `context` unchanged fields next to a change are shown
+    Tree { context: usize },
+    /// The expected and the real value next to each other, fit to `width` characters
+    SideBySide { width: usize },
+    /// A unified diff of both values pretty printed in the notation
+    Unified { notation: Notation },
+    /// The changes with their paths as one JSON object, see [crate::get_hint_json]
+    Json,
+    /// One JSON object per change
+    JsonLines,
+}
+
+/// Reads the name of a format. Its settings have their default values
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "line" => Ok(Self::Line),
+            "summary" => Ok(Self::Summary {
+                depth: 3,
+                changes: 10,
+            }),
+            "tree" => Ok(Self::Tree { context: 0 }),
+            "side-by-side" => Ok(Self::SideBySide { width: 120 }),
+            "unified" => Ok(Self::Unified {
+                notation: Notation::default(),
+            }),
+            "json" => Ok(Self::Json),
+            "jsonl" => Ok(Self::JsonLines),
+            s => Err(format!(
+                "expected line, summary, tree, side-by-side, unified, json or jsonl but got \"{s}\""
+            )),
+        }
+    }
+}
@@ -0,0 +1,218 @@
+use nom::bytes::complete::{tag, take_until};
+use nom::character::complete::{line_ending, multispace0, not_line_ending};
+use nom::error::{Error, ErrorKind};
+use nom::IResult;
+
+use crate::parser::{parse_whole_value, AssertionFailedError, ValueKind};
+
+/// Prefixes of the descriptions of matchers that describe every item of an iterable
+const ITERABLE_MATCHERS: [&str; 4] = [
+    "iterable containing ",
+    "iterable over ",
+    "iterable with items ",
+    "contains ",
+];
+
+/// demo input:
+/// java.lang.AssertionError:
+/// Expected: is <User(name=a)>
+///      but: was <User(name=b)>
+pub fn parse(input: &str) -> IResult<&str, AssertionFailedError<'_>> {
+    let (input, _) = take_until("Expected: ")(input)?;
+    let (input, _) = tag("Expected: ")(input)?;
+    let (input, expected) = not_line_ending(input)?;
+    let (input, _) = line_ending(input)?;
+    let (input, _) = multispace0(input)?;
+    let (input, _) = tag("but: ")(input)?;
+    let (input, real) = not_line_ending(input)?;
+
+    let mut warnings = vec![];
+    let (Some(expected), Some((index, real))) = (
+        parse_matcher(expected, &mut warnings),
+        parse_mismatch(real, &mut warnings),
+    ) else {
+        return Err(nom::Err::Error(Error::new(input, ErrorKind::Verify)));
+    };
+    let expected = match (index, expected) {
+        (None, expected) => expected,
+        (Some(index), ValueKind::Array(mut items)) if index < items.len() => {
+            items.swap_remove(index)
+        }
+        (Some(_), _) => return Err(nom::Err::Error(Error::new(input, ErrorKind::Verify))),
+    };
+    Ok((
+        input,
+        AssertionFailedError {
+            expected,
+            real,
+            warnings,
+        },
+    ))
+}
+
+/// Unwraps the description of a matcher
+/// demo input: is <User(name=a)>
+fn parse_matcher<'a>(description: &'a str, warnings: &mut Vec<String>) -> Option<ValueKind<'a>> {
+    let description = description.trim();
+    if let Some(inner) = description.strip_prefix("is ") {
+        return parse_matcher(inner, warnings);
+    }
+    if let Some(inner) = description
+        .strip_prefix("equalTo(")
+        .and_then(|d| d.strip_suffix(')'))
+    {
+        return parse_matcher(inner, warnings);
+    }
+    if let Some(items) = ITERABLE_MATCHERS
+        .iter()
+        .find_map(|prefix| description.strip_prefix(prefix))
+    {
+        let items = items.strip_suffix(" in any order").unwrap_or(items);
+        return parse_items(items, warnings);
+    }
+    parse_described_value(description, warnings)
+}
+
+/// demo input: was <User(name=b)>
+/// demo input: item 1: was <3>
+/// Returns the index of the mismatched item when the matcher describes every item
+fn parse_mismatch<'a>(
+    description: &'a str,
+    warnings: &mut Vec<String>,
+) -> Option<(Option<usize>, ValueKind<'a>)> {
+    let description = description.trim();
+    let (index, description) = match description.strip_prefix("item ") {
+        Some(item) => {
+            let (index, description) = item.split_once(": ")?;
+            (Some(index.parse().ok()?), description)
+        }
+        None => (None, description),
+    };
+    let value = parse_described_value(description.strip_prefix("was ")?, warnings)?;
+    Some((index, value))
+}
+
+/// Hamcrest describes values as <value> and strings as "value"
+/// demo input: <User(name=b)>
+fn parse_described_value<'a>(
+    description: &'a str,
+    warnings: &mut Vec<String>,
+) -> Option<ValueKind<'a>> {
+    let value = description
+        .strip_prefix('<')
+        .and_then(|d| d.strip_suffix('>'))
+        .unwrap_or(description);
+    parse_whole_value(value, warnings)
+}
+
+/// demo input: [<1>, "a", is <User(name=a)>]
+/// Items end at a ">" or "\"" that is followed by the next item, so the values can contain ">"
+fn parse_items<'a>(items: &'a str, warnings: &mut Vec<String>) -> Option<ValueKind<'a>> {
+    let mut rest = items.strip_prefix('[')?.strip_suffix(']')?;
+    let mut values = vec![];
+    while !rest.is_empty() {
+        let item = rest.strip_prefix("is ").unwrap_or(rest);
+        let end = item
+            .match_indices(", ")
+            .map(|(i, _)| i)
+            .find(|&i| {
+                let next = &item[i + 2..];
+                item[..i].ends_with(['>', '"'])
+                    && (next.starts_with(['<', '"']) || next.starts_with("is "))
+            })
+            .unwrap_or(item.len());
+        values.push(parse_described_value(&item[..end], warnings)?);
+        rest = item.get(end + 2..).unwrap_or_default();
+    }
+    Some(ValueKind::Array(values))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        hamcrest::parse,
+        parser::{AssertionFailedError, Dto, DtoField, ValueKind},
+    };
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parse_is() {
+        let input = "java.lang.AssertionError: \nExpected: is <User(name=a)>\n     but: was <User(name=b)>\n\tat org.hamcrest.MatcherAssert.assertThat(MatcherAssert.java:20)";
+        let user = |name: &'static str| {
+            ValueKind::Dto(Dto {
+                name: "User",
+                fields: vec![DtoField {
+                    name: "name".into(),
+                    value: ValueKind::String(name.into()),
+                }],
+                ..Default::default()
+            })
+        };
+
+        assert_eq!(
+            parse(input),
+            Ok((
+                "\n\tat org.hamcrest.MatcherAssert.assertThat(MatcherAssert.java:20)",
+                AssertionFailedError {
+                    expected: user("a"),
+                    real: user("b"),
+                    warnings: vec![],
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_strings() {
+        let input = "Expected: is equalTo(\"Hello\")\n     but: was \"World\"";
+        let expected = AssertionFailedError {
+            expected: ValueKind::Quoted("Hello".into()),
+            real: ValueKind::Quoted("World".into()),
+            warnings: vec![],
+        };
+
+        assert_eq!(parse(input), Ok(("", expected)));
+    }
+
+    #[test]
+    fn parse_null() {
+        let input = "Expected: <1>\n     but: was null";
+        let expected = AssertionFailedError {
+            expected: ValueKind::Integer("1".into()),
+            real: ValueKind::Null,
+            warnings: vec![],
+        };
+
+        assert_eq!(parse(input), Ok(("", expected)));
+    }
+
+    #[test]
+    fn parse_contains() {
+        let input = "Expected: iterable containing [<1>, <2>]\n     but: was <[1, 3]>";
+        let expected = AssertionFailedError {
+            expected: ValueKind::Array(vec![
+                ValueKind::Integer("1".into()),
+                ValueKind::Integer("2".into()),
+            ]),
+            real: ValueKind::Array(vec![
+                ValueKind::Integer("1".into()),
+                ValueKind::Integer("3".into()),
+            ]),
+            warnings: vec![],
+        };
+
+        assert_eq!(parse(input), Ok(("", expected)));
+    }
+
+    #[test]
+    fn parse_contains_item() {
+        let input = "Expected: iterable containing [\"a\", \"b\"]\n     but: item 1: was \"c\"";
+        let expected = AssertionFailedError {
+            expected: ValueKind::Quoted("b".into()),
+            real: ValueKind::Quoted("c".into()),
+            warnings: vec![],
+        };
+
+        assert_eq!(parse(input), Ok(("", expected)));
+    }
+}
@@ -0,0 +1,181 @@
+use itertools::Itertools;
+
+use crate::{
+    change::{Change, ChangeKind},
+    pretty::{json as value, quote},
+};
+
+/// Version of the JSON output. It changes when fields are renamed or removed, not when they are added
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Fields that tell where an assertion failure was found like its test or log line: ("line", "42").
+/// The values are JSON
+pub type Source<'s> = [(&'s str, String)];
+
+/// The changes of an assertion as one JSON object:
+/// `{"schema_version":1,"equal":false,"ignored":0,"warnings":[],"changes":[..]}`
+pub fn document(
+    changes: &[Change],
+    ignored: usize,
+    warnings: &[String],
+    source: &Source,
+) -> String {
+    format!(
+        "{{{},\"changes\":[{}]}}",
+        summary(changes, ignored, warnings, source),
+        changes.iter().map(|c| change(c, "")).join(",")
+    )
+}
+
+/// One JSON object per change and line, each with the schema version and the source.
+/// The last line is like [document] with the amount of changes instead of the changes:
+/// `{"schema_version":1,"equal":false,"ignored":0,"warnings":[],"changes":2}`
+pub fn lines(changes: &[Change], ignored: usize, warnings: &[String], source: &Source) -> String {
+    let head = format!("\"schema_version\":{SCHEMA_VERSION},{}", fields(source));
+    let summary = format!(
+        "{{{},\"changes\":{}}}",
+        summary(changes, ignored, warnings, source),
+        changes.len()
+    );
+    changes
+        .iter()
+        .map(|c| change(c, &head))
+        .chain([summary])
+        .join("\n")
+}
+
+fn summary(changes: &[Change], ignored: usize, warnings: &[String], source: &Source) -> String {
+    format!(
+        "\"schema_version\":{SCHEMA_VERSION},{}\"equal\":{},\"ignored\":{ignored},\"warnings\":[{}]",
+        fields(source),
+        changes.is_empty(),
+        warnings.iter().map(|w| quote(w)).join(",")
+    )
+}
+
+fn fields(source: &Source) -> String {
+    source
+        .iter()
+        .map(|(key, value)| format!("{}:{value},", quote(key)))
+        .join("")
+}
+
+/// `{"path":"$.name","kind":"changed","expected":"a","actual":"b"}`.
+/// The side that does not have the value is null
+fn change(change: &Change, head: &str) -> String {
+    let side = |v: &Option<_>| v.as_ref().map(value).unwrap_or_else(|| "null".to_string());
+    format!(
+        "{{{head}\"path\":{},\"kind\":\"{}\",\"expected\":{},\"actual\":{}}}",
+        quote(&change.path),
+        kind(change.kind),
+        side(&change.expected),
+        side(&change.actual)
+    )
+}
+
+fn kind(kind: ChangeKind) -> &'static str {
+    match kind {
+        ChangeKind::Changed => "changed",
+        ChangeKind::Added => "added",
+        ChangeKind::Removed => "removed",
+        ChangeKind::Moved => "moved",
+        ChangeKind::TypeMismatch => "type_mismatch",
+        ChangeKind::ClassChange => "class_change",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{get_hint_json, get_hint_with_format, DiffOptions, Format};
+    use pretty_assertions::assert_eq;
+
+    const USER: &str = "org.opentest4j.AssertionFailedError: expected: <User(id=1, name=a, tags=[x])> but was: <User(id=1, name=\"b\", tags=[x, Tag(v=1)])>";
+
+    #[test]
+    fn json_document() {
+        assert_eq!(
+            get_hint_json(USER, &DiffOptions::default()),
+            Some(
+                concat!(
+                    "{\"schema_version\":1,\"equal\":false,\"ignored\":0,\"warnings\":[],\"changes\":[",
+                    "{\"path\":\"$.name\",\"kind\":\"changed\",\"expected\":\"a\",\"actual\":\"b\"},",
+                    "{\"path\":\"$.tags[1]\",\"kind\":\"added\",\"expected\":null,\"actual\":{\"$class\":\"Tag\",\"v\":1}}",
+                    "]}"
+                )
+                .to_string()
+            )
+        );
+        assert_eq!(
+            get_hint_json(
+                "org.opentest4j.AssertionFailedError: expected: <1> but was: <1>",
+                &DiffOptions::default()
+            ),
+            Some("{\"schema_version\":1,\"equal\":true,\"ignored\":0,\"warnings\":[],\"changes\":[]}".to_string())
+        );
+    }
+
+    #[test]
+    fn json_lines() {
+        assert_eq!(
+            get_hint_with_format(USER, true, &DiffOptions::default(), Format::JsonLines),
+            Some(
+                [
+                    "{\"schema_version\":1,\"path\":\"$.name\",\"kind\":\"changed\",\"expected\":\"a\",\"actual\":\"b\"}",
+                    "{\"schema_version\":1,\"path\":\"$.tags[1]\",\"kind\":\"added\",\"expected\":null,\"actual\":{\"$class\":\"Tag\",\"v\":1}}",
+                    "{\"schema_version\":1,\"equal\":false,\"ignored\":0,\"warnings\":[],\"changes\":2}",
+                ]
+                .join("\n")
+            )
+        );
+        let options = DiffOptions {
+            ignore: vec!["id".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            get_hint_with_format(
+                "org.opentest4j.AssertionFailedError: expected: <Job(id=1, call=f(x, y))> but was: <Job(id=2, call=f(x, y))>",
+                false,
+                &options,
+                Format::JsonLines
+            ),
+            Some(concat!(
+                "{\"schema_version\":1,\"equal\":true,\"ignored\":1,\"warnings\":",
+                "[\"f(x, y) of call could be a Dto without field names, it was read as text\"],\"changes\":0}"
+            ).to_string())
+        );
+    }
+
+    #[test]
+    fn json_values() {
+        assert_eq!(
+            get_hint_json(
+                "org.opentest4j.AssertionFailedError: expected: <Child(super=Parent(id=1), super=a, zip=01234)> but was: <Child(super=Parent(id=2), super=a, zip=01234)>",
+                &DiffOptions::default()
+            ),
+            Some(
+                concat!(
+                    "{\"schema_version\":1,\"equal\":false,\"ignored\":0,\"warnings\":[],\"changes\":[",
+                    "{\"path\":\"$.super.id\",\"kind\":\"changed\",\"expected\":1,\"actual\":2}",
+                    "]}"
+                )
+                .to_string()
+            )
+        );
+        assert_eq!(
+            get_hint_json(
+                "org.opentest4j.AssertionFailedError: expected: <[Child(super=Parent(id=1), super=a, zip=01234)]> but was: <[]>",
+                &DiffOptions::default()
+            ),
+            Some(
+                concat!(
+                    "{\"schema_version\":1,\"equal\":false,\"ignored\":0,\"warnings\":[],\"changes\":[",
+                    "{\"path\":\"$[0]\",\"kind\":\"removed\",\"expected\":",
+                    "{\"$class\":\"Child\",\"$super\":{\"$class\":\"Parent\",\"id\":1},\"super\":\"a\",\"zip\":\"01234\"},",
+                    "\"actual\":null}",
+                    "]}"
+                )
+                .to_string()
+            )
+        );
+    }
+}
@@ -0,0 +1,234 @@
+use nom::branch::alt;
+use nom::bytes::complete::{tag, take_until};
+use nom::character::complete::{not_line_ending, space0};
+use nom::error::{Error, ErrorKind};
+use nom::IResult;
+
+use crate::parser::{parse_whole_value, AssertionFailedError, ValueKind};
+
+/// Parses the failure messages of JUnit 4
+pub fn parse(input: &str) -> IResult<&str, AssertionFailedError<'_>> {
+    alt((parse_expected_but_was, parse_expected_null))(input)
+}
+
+/// demo input: org.junit.ComparisonFailure: expected:<[a]bc> but was:<[x]bc>
+/// demo input: java.lang.AssertionError: expected:<User(name=a)> but was:<User(name=b)>
+fn parse_expected_but_was(input: &str) -> IResult<&str, AssertionFailedError<'_>> {
+    let (input, before) = take_until("expected:")(input)?;
+    let (input, _) = tag("expected:")(input)?;
+    let (input, _) = space0(input)?;
+    let (input, _) = tag("<")(input)?;
+    let (input, expected) = take_until("> but was:")(input)?;
+    let (input, _) = tag("> but was:")(input)?;
+    let (input, _) = space0(input)?;
+    let (input, _) = tag("<")(input)?;
+    let (_, line) = not_line_ending(input)?;
+    let Some(end) = line.rfind('>') else {
+        return fail(input);
+    };
+    let real = &line[..end];
+    let input = &input[end + 1..];
+
+    // ComparisonFailure marks the part that differs with "[]"
+    let comparison_failure = before
+        .rsplit('\n')
+        .next()
+        .is_some_and(|line| line.contains("ComparisonFailure"));
+    let mut warnings = vec![];
+    let values = comparison_failure
+        .then(|| marked_strings(expected, real))
+        .flatten()
+        .or_else(|| {
+            parse_whole_value(expected, &mut warnings).zip(parse_whole_value(real, &mut warnings))
+        })
+        .or_else(|| marked_strings(expected, real));
+
+    let Some((expected, real)) = values else {
+        return fail(input);
+    };
+    Ok((
+        input,
+        AssertionFailedError {
+            expected,
+            real,
+            warnings,
+        },
+    ))
+}
+
+/// demo input: java.lang.AssertionError: expected null, but was:<User(name=a)>
+fn parse_expected_null(input: &str) -> IResult<&str, AssertionFailedError<'_>> {
+    let (input, _) = take_until("expected null, but was:")(input)?;
+    let (input, _) = tag("expected null, but was:")(input)?;
+    let (input, _) = space0(input)?;
+    let (input, _) = tag("<")(input)?;
+    let (_, line) = not_line_ending(input)?;
+    let Some(end) = line.rfind('>') else {
+        return fail(input);
+    };
+    let mut warnings = vec![];
+    let Some(real) = parse_whole_value(&line[..end], &mut warnings) else {
+        return fail(input);
+    };
+    Ok((
+        &input[end + 1..],
+        AssertionFailedError {
+            expected: ValueKind::Null,
+            real,
+            warnings,
+        },
+    ))
+}
+
+fn marked_strings<'a>(expected: &str, real: &str) -> Option<(ValueKind<'a>, ValueKind<'a>)> {
+    let (expected, real) = strip_markers(expected, real)?;
+    Some((
+        ValueKind::String(expected.into()),
+        ValueKind::String(real.into()),
+    ))
+}
+
+/// Removes the "[]" that ComparisonFailure puts around the part that differs.
+/// demo input: "[a]bc", "[x]bc"
+fn strip_markers(expected: &str, real: &str) -> Option<(String, String)> {
+    let prefix = common_prefix(expected, real);
+    let open = expected[..prefix].rfind('[')?;
+    let suffix = common_suffix(&expected[open + 1..], &real[open + 1..]);
+    let expected_close = expected.len() - suffix + expected[expected.len() - suffix..].find(']')?;
+    let real_close = real.len() - suffix + real[real.len() - suffix..].find(']')?;
+
+    let strip = |value: &str, close: usize| {
+        format!(
+            "{}{}{}",
+            &value[..open],
+            &value[open + 1..close],
+            &value[close + 1..]
+        )
+    };
+    Some((strip(expected, expected_close), strip(real, real_close)))
+}
+
+/// Length in bytes of the common prefix
+fn common_prefix(a: &str, b: &str) -> usize {
+    a.char_indices()
+        .zip(b.chars())
+        .find(|((_, a), b)| a != b)
+        .map(|((i, _), _)| i)
+        .unwrap_or(a.len().min(b.len()))
+}
+
+/// Length in bytes of the common suffix
+fn common_suffix(a: &str, b: &str) -> usize {
+    a.chars()
+        .rev()
+        .zip(b.chars().rev())
+        .take_while(|(a, b)| a == b)
+        .map(|(a, _)| a.len_utf8())
+        .sum()
+}
+
+fn fail<T>(input: &str) -> IResult<&str, T> {
+    Err(nom::Err::Error(Error::new(input, ErrorKind::Verify)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        junit4::{parse, strip_markers},
+        parser::{AssertionFailedError, Dto, DtoField, ValueKind},
+    };
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn strip_markers_test() {
+        assert_eq!(
+            strip_markers("[a]bc", "[x]bc"),
+            Some(("abc".to_string(), "xbc".to_string()))
+        );
+        assert_eq!(
+            strip_markers("ab[]c", "ab[x]c"),
+            Some(("abc".to_string(), "abxc".to_string()))
+        );
+        assert_eq!(
+            strip_markers("...aaa[b]]...", "...aaa[c]]..."),
+            Some(("...aaab]...".to_string(), "...aaac]...".to_string()))
+        );
+        assert_eq!(strip_markers("abc", "xbc"), None);
+    }
+
+    #[test]
+    fn parse_comparison_failure() {
+        let input = "org.junit.ComparisonFailure: expected:<[a]bc> but was:<[x]bc>\n\tat org.junit.Assert.assertEquals(Assert.java:115)";
+        let expected = AssertionFailedError {
+            expected: ValueKind::String("abc".into()),
+            real: ValueKind::String("xbc".into()),
+            warnings: vec![],
+        };
+
+        assert_eq!(
+            parse(input),
+            Ok((
+                "\n\tat org.junit.Assert.assertEquals(Assert.java:115)",
+                expected
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_assertion_error() {
+        let input =
+            "java.lang.AssertionError: message expected:<User(name=a)> but was:<User(name=b)>";
+        let user = |name: &'static str| {
+            ValueKind::Dto(Dto {
+                name: "User",
+                fields: vec![DtoField {
+                    name: "name".into(),
+                    value: ValueKind::String(name.into()),
+                }],
+                ..Default::default()
+            })
+        };
+
+        assert_eq!(
+            parse(input),
+            Ok((
+                "",
+                AssertionFailedError {
+                    expected: user("a"),
+                    real: user("b"),
+                    warnings: vec![],
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_array() {
+        let input = "java.lang.AssertionError: expected:<[1, 2]> but was:<[1, 3]>";
+        let expected = AssertionFailedError {
+            expected: ValueKind::Array(vec![
+                ValueKind::Integer("1".into()),
+                ValueKind::Integer("2".into()),
+            ]),
+            real: ValueKind::Array(vec![
+                ValueKind::Integer("1".into()),
+                ValueKind::Integer("3".into()),
+            ]),
+            warnings: vec![],
+        };
+
+        assert_eq!(parse(input), Ok(("", expected)));
+    }
+
+    #[test]
+    fn parse_expected_null() {
+        let input = "java.lang.AssertionError: expected null, but was:<a>";
+        let expected = AssertionFailedError {
+            expected: ValueKind::Null,
+            real: ValueKind::String("a".into()),
+            warnings: vec![],
+        };
+
+        assert_eq!(parse(input), Ok(("", expected)));
+    }
+}
@@ -1,22 +1,172 @@
+mod assertj;
+mod change;
 mod code;
+mod config;
 mod diff;
+mod format;
+mod hamcrest;
+mod json;
+mod junit4;
 mod message;
 mod parser;
+mod pretty;
+mod report;
+mod scan;
+mod side_by_side;
+mod summary;
+mod tree;
+mod unified;
+
+pub use change::{changes, Change, ChangeKind};
+pub use config::{parse_tolerance, read_config};
+pub use diff::{DiffOptions, Granularity, Subtlety, Tolerance};
+pub use format::Format;
+pub use json::SCHEMA_VERSION;
+pub use parser::{Dto, DtoField, DtoStyle, ValueKind};
+pub use pretty::Notation;
+pub use report::ReportHint;
+pub use scan::{ScanHint, Scanner};
 
 #[cfg(test)]
 mod test_data;
 
 pub fn get_hint(text: &str, color: bool) -> Option<String> {
+    get_hint_with_options(text, color, &DiffOptions::default())
+}
+
+pub fn get_hint_with_options(text: &str, color: bool, options: &DiffOptions) -> Option<String> {
+    get_hint_with_format(text, color, options, Format::Line)
+}
+
+/// Returns the hint written in the format
+pub fn get_hint_with_format(
+    text: &str,
+    color: bool,
+    options: &DiffOptions,
+    format: Format,
+) -> Option<String> {
+    parse_hint(text, color, options, format, &[])
+}
+
+fn parse_hint(
+    text: &str,
+    color: bool,
+    options: &DiffOptions,
+    format: Format,
+    source: &json::Source,
+) -> Option<String> {
     match parser::parse(text) {
-        Ok((_, data)) => {
-            let difference = diff::diff(data.expected, data.real);
-            let message = message::message(difference, color);
-            Some(message)
+        Ok((_, data)) => Some(hint_message(data, color, options, format, source)),
+        Err(_) => None,
+    }
+}
+
+/// Writes the difference of the failure in the format. JSON has no newline in front
+/// and adds the source of the failure to its objects
+fn hint_message(
+    data: parser::AssertionFailedError<'_>,
+    color: bool,
+    options: &DiffOptions,
+    format: Format,
+    source: &json::Source,
+) -> String {
+    let (expected, real) = (data.expected, data.real);
+    let (difference, ignored) = diff::diff(expected.clone(), real.clone(), options);
+    let mut hint = match format {
+        Format::Line => message::message(difference, color),
+        Format::Summary { depth, changes } => summary::summary(difference, color, depth, changes),
+        Format::Tree { context } => tree::tree(difference, &expected, &real, color, context),
+        Format::SideBySide { .. } | Format::Unified { .. }
+            if difference == diff::Difference::Equal =>
+        {
+            String::new()
+        }
+        Format::Unified { notation } => {
+            let changes = change::from_difference(difference, &expected, &real);
+            unified::unified(&expected, &real, &changes, color, notation)
         }
+        Format::SideBySide { width } => {
+            let changes = change::from_difference(difference, &expected, &real);
+            side_by_side::side_by_side(&expected, &real, &changes, color, width)
+        }
+        Format::Json => {
+            let changes = change::from_difference(difference, &expected, &real);
+            return json::document(&changes, ignored, &data.warnings, source);
+        }
+        Format::JsonLines => {
+            let changes = change::from_difference(difference, &expected, &real);
+            return json::lines(&changes, ignored, &data.warnings, source);
+        }
+    };
+    // patch and delta would read the notes as part of the unified diff
+    if let Format::Unified { .. } = format {
+        return hint;
+    }
+    let mut notes = data
+        .warnings
+        .iter()
+        .map(|warning| message::warning(warning, color))
+        .collect::<Vec<_>>();
+    if ignored > 0 {
+        notes.insert(0, format!(" ({ignored} ignored)"));
+    }
+    for note in notes {
+        match format {
+            Format::Line | Format::Summary { .. } => hint.push_str(&note),
+            // Notes behind the last line would be read as part of it
+            _ => hint.push_str(&format!("\n{}", note.trim_start())),
+        }
+    }
+    hint
+}
+
+/// Returns the changes between the expected and the real value of an assertion message
+pub fn get_changes<'a>(text: &'a str, options: &DiffOptions) -> Option<Vec<Change<'a>>> {
+    match parser::parse(text) {
+        Ok((_, data)) => Some(changes(data.expected, data.real, options)),
         Err(_) => None,
     }
 }
 
+/// Returns the changes of an assertion message as JSON with a `schema_version`. See the README for the schema
+pub fn get_hint_json(text: &str, options: &DiffOptions) -> Option<String> {
+    parse_hint(text, false, options, Format::Json, &[])
+}
+
+/// Returns a hint for every failed test case of a Surefire or Gradle XML report (TEST-*.xml)
+pub fn get_report_hints(
+    xml: &str,
+    color: bool,
+    options: &DiffOptions,
+    format: Format,
+) -> Vec<ReportHint> {
+    report::failures(xml)
+        .into_iter()
+        .filter_map(|failure| {
+            let test = format!("{}.{}", failure.class_name, failure.name);
+            let source = [("test", pretty::quote(&test))];
+            let hint = parse_hint(&failure.message, color, options, format, &source)
+                .or_else(|| parse_hint(&failure.text, color, options, format, &source))?;
+            Some(ReportHint {
+                class_name: failure.class_name,
+                name: failure.name,
+                hint,
+            })
+        })
+        .collect()
+}
+
+/// Returns a hint for every assertion failure of a build log like the output of `mvn test` or `gradle test`.
+/// The log is read line by line
+pub fn scan_hints<R: std::io::BufRead>(
+    reader: R,
+    color: bool,
+    options: DiffOptions,
+    format: Format,
+) -> Scanner<R> {
+    Scanner::new(reader, color, options, format)
+}
+
 pub fn to_code(text: &str) -> Option<String> {
     match parser::parse_value_kind(text) {
         Ok((_, value)) => {
@@ -29,8 +179,12 @@ pub fn to_code(text: &str) -> Option<String> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{get_hint, to_code};
+    use crate::{
+        get_changes, get_hint, get_hint_with_options, get_report_hints, to_code, DiffOptions,
+        Format, ReportHint, Tolerance,
+    };
     use pretty_assertions::assert_eq;
+    use std::time::Duration;
 
     #[test]
     fn string_hint() {
@@ -40,7 +194,27 @@ mod tests {
                 "org.opentest4j.AssertionFailedError: expected: <1> but was: <2>",
                 false
             ),
-            Some(" -> 2".to_string())
+            Some(" -> 2 (off by 1)".to_string())
+        );
+    }
+
+    #[test]
+    fn type_hint() {
+        assert_eq!(
+            get_hint(
+                "org.opentest4j.AssertionFailedError: expected: <\"1\"> but was: <1>",
+                false
+            ),
+            Some(" \"String\" and \"Integer\" are not the same Type (\"1\" -> 1)".to_string())
+        );
+        assert_eq!(
+            get_hint(
+                "org.opentest4j.AssertionFailedError: expected: <\"true\"> but was: <true>",
+                false
+            ),
+            Some(
+                " \"String\" and \"Boolean\" are not the same Type (\"true\" -> true)".to_string()
+            )
         );
     }
 
@@ -51,7 +225,7 @@ mod tests {
                 "org.opentest4j.AssertionFailedError: expected: <User(firstName=null, lastname=asd)> but was: <User(firstName=null, lastname=aaa)>",
                 false
             ),
-            Some(" -> User(.lastname -> aaa)".to_string())
+            Some(" -> User(.lastname -> [-asd-]{+aaa+})".to_string())
         );
     }
 
@@ -62,10 +236,318 @@ mod tests {
                 "org.opentest4j.AssertionFailedError: expected: <User(name=1, other=User(name=2, other=null))> but was: <User(name=1, other=null)>",
                 false
             ),
-            Some(" -> User(.other \"User\" and \"null\" are not the same Type)".to_string())
+            Some(" -> User(.other \"User\" and \"null\" are not the same Type (User(name=2,other=null) -> null))".to_string())
+        );
+    }
+
+    #[test]
+    fn kotlin_hint() {
+        assert_eq!(
+            get_hint(
+                "org.opentest4j.AssertionFailedError: expected: <Order(order_id=1, items=[(apple, 2)], $delegate=null)> but was: <Order(order_id=1, items=[(apple, 3)], $delegate=null)>",
+                false
+            ),
+            Some(" -> Order(.items -> [ changed at [0] -> [ changed at [1] -> 3 (off by 1)]])".to_string())
+        );
+    }
+
+    #[test]
+    fn record_hint() {
+        assert_eq!(
+            get_hint(
+                "org.opentest4j.AssertionFailedError: expected: <[User[name=first, other=null]]> but was: <[User[name=first, other=null], User[name=second, other=null]]>",
+                false
+            ),
+            Some(" -> [ inserted at [1] User[name=\"second\",other=null]]".to_string())
+        );
+    }
+
+    #[test]
+    fn lombok_hint() {
+        assert_eq!(
+            get_hint(
+                "org.opentest4j.AssertionFailedError: expected: <Outer.Child(super=Parent(id=1), first, null)> but was: <Outer.Child(super=Parent(id=2), first, x)>",
+                false
+            ),
+            Some(" -> Outer.Child(.super -> Parent(.id -> 2 (off by 1)).1 \"null\" and \"String\" are not the same Type (null -> \"x\"))".to_string())
+        );
+    }
+
+    #[test]
+    fn field_name_hint() {
+        assert_eq!(
+            get_hint(
+                "org.opentest4j.AssertionFailedError: expected: <User(name=a, age=1)> but was: <User(age=1, name=a)>",
+                false
+            ),
+            Some("".to_string())
+        );
+        assert_eq!(
+            get_hint(
+                "org.opentest4j.AssertionFailedError: expected: <User(id=1, name=a, age=1)> but was: <User(id=1, email=x, age=2)>",
+                false
+            ),
+            Some(" -> User( missing field name=\"a\".age -> 2 (off by 1) extra field email=\"x\")".to_string())
+        );
+    }
+
+    #[test]
+    fn delimiter_hint() {
+        assert_eq!(
+            get_hint(
+                "org.opentest4j.AssertionFailedError: expected: <Rule(description=Hello, world, expr=a>b, type=List<String>)> but was: <Rule(description=Hello, world, expr=a>c, type=List<String>)>",
+                false
+            ),
+            Some(" -> Rule(.expr -> a>[-b-]{+c+})".to_string())
+        );
+    }
+
+    #[test]
+    fn unbalanced_hint() {
+        assert_eq!(
+            get_hint(
+                "org.opentest4j.AssertionFailedError: expected: <Note(text=:), likes=1)> but was: <Note(text=:), likes=2)>",
+                false
+            ),
+            Some(" -> Note(.likes -> 2 (off by 1)) (warning: the brackets of Note are not balanced, its fields were split at their names)".to_string())
+        );
+    }
+
+    #[test]
+    fn map_hint() {
+        assert_eq!(
+            get_hint(
+                "org.opentest4j.AssertionFailedError: expected: <{a=1, b=2}> but was: <{b=2, a=1}>",
+                false
+            ),
+            Some("".to_string())
+        );
+        assert_eq!(
+            get_hint(
+                "org.opentest4j.AssertionFailedError: expected: <{a=1, b=2, c=3}> but was: <{d=4, b=3, a=1}>",
+                false
+            ),
+            Some(" -> [.b -> 3 (off by 1) missing c=3 additional d=4]".to_string())
+        );
+        assert_eq!(
+            get_hint(
+                "org.opentest4j.AssertionFailedError: expected: <Map(e={})> but was: <Map(e={k=v})>",
+                false
+            ),
+            Some(" -> Map(.e -> [ additional k=\"v\"])".to_string())
+        );
+        assert_eq!(
+            get_hint(
+                "org.opentest4j.AssertionFailedError: expected: <{k=v}> but was: <{}>",
+                false
+            ),
+            Some(" -> [ missing k=\"v\"]".to_string())
+        );
+    }
+
+    #[test]
+    fn unordered_hint() {
+        let input =
+            "org.opentest4j.AssertionFailedError: expected: <[a, b, c]> but was: <[c, a, b]>";
+        let options = DiffOptions {
+            unordered_lists: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            get_hint_with_options(input, false, &options),
+            Some("".to_string())
+        );
+        assert_eq!(
+            get_hint_with_options(
+                "org.opentest4j.AssertionFailedError: expected: <[a, b, c]> but was: <[b, d, a]>",
+                false,
+                &options
+            ),
+            Some(" -> [ changed at [2] -> [-c-]{+d+}]".to_string())
+        );
+        assert_eq!(
+            get_hint(input, false),
+            Some(" -> [ moved [2] -> [0]]".to_string())
+        );
+    }
+
+    #[test]
+    fn aligned_list_hint() {
+        assert_eq!(
+            get_hint(
+                "org.opentest4j.AssertionFailedError: expected: <[a, b, c, d, e]> but was: <[a, x, b, d, ee]>",
+                false
+            ),
+            Some(" -> [ inserted at [1] \"x\" removed at [2] \"c\" changed at [4] -> e{+e+}]".to_string())
+        );
+    }
+
+    #[test]
+    fn identity_hint() {
+        assert_eq!(
+            get_hint(
+                "org.opentest4j.AssertionFailedError: expected: <[User(id=1, name=a), User(id=2, name=b)]> but was: <[User(id=2, name=c), User(id=1, name=a)]>",
+                false
+            ),
+            Some(" -> [ changed at [1] User(id=2) -> User(.name -> [-b-]{+c+})]".to_string())
+        );
+        assert_eq!(
+            get_hint(
+                "org.opentest4j.AssertionFailedError: expected: <[User(id=1, name=a)]> but was: <[User(id=3, name=a)]>",
+                false
+            ),
+            Some(" -> [ removed at [0] User(id=1,name=\"a\") inserted at [0] User(id=3,name=\"a\")]".to_string())
+        );
+    }
+
+    #[test]
+    fn similarity_hint() {
+        assert_eq!(
+            get_hint(
+                "org.opentest4j.AssertionFailedError: expected: <[User(name=a, age=1, city=x), User(name=b, age=2, city=y)]> but was: <[User(name=b, age=3, city=y), User(name=a, age=1, city=z)]>",
+                false
+            ),
+            Some(" -> [ changed at [0] -> User(.city -> [-x-]{+z+}) changed at [1] -> User(.age -> 3 (off by 1))]".to_string())
+        );
+    }
+
+    #[test]
+    fn ignore_hint() {
+        let options = DiffOptions {
+            ignore: vec!["createdAt".to_string(), "Order.*.id".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            get_hint_with_options(
+                "org.opentest4j.AssertionFailedError: expected: <Order(id=1, user=User(id=7, name=a), createdAt=2024-01-01T00:00)> but was: <Order(id=2, user=User(id=8, name=a), createdAt=2024-05-01T10:00)>",
+                false,
+                &options
+            ),
+            Some(" -> Order(.id -> 2 (off by 1)) (2 ignored)".to_string())
+        );
+        assert_eq!(
+            get_hint_with_options(
+                "org.opentest4j.AssertionFailedError: expected: <User(id=1, meta={createdAt=a, b=1})> but was: <User(id=1, meta={b=1})>",
+                false,
+                &options
+            ),
+            Some(" (1 ignored)".to_string())
         );
     }
 
+    #[test]
+    fn tolerance_hint() {
+        let options = DiffOptions {
+            tolerances: vec![
+                ("price".to_string(), Tolerance::Absolute(0.01)),
+                (
+                    "**".to_string(),
+                    Tolerance::Duration(Duration::from_millis(5)),
+                ),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            get_hint_with_options(
+                "org.opentest4j.AssertionFailedError: expected: <Item(price=1.005, weight=1.005, at=2024-01-01T00:00:00.001Z)> but was: <Item(price=1.01, weight=1.01, at=2024-01-01T01:00:00.004+01:00)>",
+                false,
+                &options
+            ),
+            Some(" -> Item(.weight -> 1.01 (off by 0.005))".to_string())
+        );
+        assert_eq!(
+            get_hint_with_options(
+                "org.opentest4j.AssertionFailedError: expected: <Item(at=2024-01-01T00:00:00Z)> but was: <Item(at=2024-01-01T00:00:01Z)>",
+                false,
+                &options
+            ),
+            Some(" -> Item(.at -> 2024-01-01T00:00:0[-0-]{+1+}Z)".to_string())
+        );
+    }
+
+    #[test]
+    fn changes() {
+        let out = get_changes(
+            "org.opentest4j.AssertionFailedError: expected: <User(id=1, meta={a=1, b=2}, tags=[x])> but was: <User(meta={a=1}, tags=[x, y], name=z)>",
+            &DiffOptions::default(),
+        )
+        .map(|changes| {
+            changes
+                .into_iter()
+                .map(|c| format!("{:?} {}", c.kind, c.path))
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(
+            out,
+            Some(vec![
+                "Removed $.id".to_string(),
+                "Removed $.meta[\"b\"]".to_string(),
+                "Added $.tags[1]".to_string(),
+                "Added $.name".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn subtle_hint() {
+        let hint = |expected: &str, real: &str| {
+            get_hint(
+                &format!(
+                    "org.opentest4j.AssertionFailedError: expected: <User(name={expected})> but was: <User(name={real})>"
+                ),
+                false,
+            )
+        };
+        assert_eq!(
+            hint("a b", "a  b"),
+            Some(
+                " -> User(.name -> a\u{2420}\u{2420}b (only whitespace differs from a\u{2420}b))"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            hint("a b", "a\u{a0}b"),
+            Some(
+                " -> User(.name -> a\\u00a0b (only whitespace differs from a\u{2420}b))"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            hint("Smith", "SMITH"),
+            Some(" -> User(.name -> SMITH (only the case differs from Smith))".to_string())
+        );
+        assert_eq!(
+            hint("caf\u{e9}", "cafe\u{301}"),
+            Some(" -> User(.name -> cafe\\u0301 (only the Unicode normalization differs from caf\\u00e9))".to_string())
+        );
+    }
+
+    #[test]
+    fn empty_list_hint() {
+        let hint = |expected: &str, real: &str| {
+            get_hint(
+                &format!(
+                    "org.opentest4j.AssertionFailedError: expected: <{expected}> but was: <{real}>"
+                ),
+                false,
+            )
+        };
+        assert_eq!(
+            hint("[]", "[a]"),
+            Some(" -> [ inserted at [0] \"a\"]".to_string())
+        );
+        assert_eq!(
+            hint("[a]", "[]"),
+            Some(" -> [ removed at [0] \"a\"]".to_string())
+        );
+        assert_eq!(
+            hint("User(tags=[])", "User(tags=[x, y])"),
+            Some(" -> User(.tags -> [ inserted at [0] \"x\" inserted at [1] \"y\"])".to_string())
+        );
+        assert_eq!(hint("User(tags=[])", "User(tags=[])"), Some("".to_string()));
+    }
+
     #[test]
     fn list_hint() {
         assert_eq!(
@@ -73,7 +555,7 @@ mod tests {
                 "org.opentest4j.AssertionFailedError: expected: <[User(name=first, other=null)]> but was: <[User(name=first, other=null), User(name=second, other=null)]>",
                 false
             ),
-            Some(" -> [ additional User(name=\"second\",other=null)]".to_string())
+            Some(" -> [ inserted at [1] User(name=\"second\",other=null)]".to_string())
         );
     }
 
@@ -84,7 +566,79 @@ mod tests {
                 "org.opentest4j.AssertionFailedError: expected: <Complicated(a=hey, b=2, c=500, d=600, e={eee=Complicated(a=a, b=2, c=500, d=600, e={}, f=[], g=[])}, f=[Complicated(a=thing, b=2, c=500, d=600, e={}, f=[], g=[])], g=[Complicated(a=hehe, b=2, c=500, d=600, e={}, f=[], g=[])])> but was: <Complicated(a=hey, b=2, c=500, d=600, e={eee=Complicated(a=b, b=2, c=500, d=600, e={}, f=[], g=[])}, f=[Complicated(a=thing, b=2, c=500, d=600, e={}, f=[], g=[])], g=[Complicated(a=hehe, b=2, c=500, d=600, e={}, f=[], g=[])])>",
                 false
             ),
-            Some(" -> Complicated(.e -> [.eee -> Complicated(.a -> b)])".to_string())
+            Some(" -> Complicated(.e -> [.eee -> Complicated(.a -> [-a-]{+b+})])".to_string())
+        );
+    }
+
+    #[test]
+    fn assertj_hint() {
+        assert_eq!(
+            get_hint(
+                "org.opentest4j.AssertionFailedError: \nexpected: User(firstName=null, lastname=asd)\n but was: User(firstName=null, lastname=aaa)",
+                false
+            ),
+            Some(" -> User(.lastname -> [-asd-]{+aaa+})".to_string())
+        );
+    }
+
+    #[test]
+    fn hamcrest_hint() {
+        assert_eq!(get_hint("java.lang.AssertionError: \nExpected: is <User(firstName=null, lastname=asd)>\n     but: was <User(firstName=null, lastname=aaa)>", false),
+            Some(" -> User(.lastname -> [-asd-]{+aaa+})".to_string())
+        );
+    }
+
+    #[test]
+    fn junit4_hint() {
+        assert_eq!(
+            get_hint(
+                "org.junit.ComparisonFailure: expected:<[a]sd> but was:<[b]sd>",
+                false
+            ),
+            Some(" -> [-a-]{+b+}sd".to_string())
+        );
+        assert_eq!(
+            get_hint(
+                "java.lang.AssertionError: expected:<User(firstName=null, lastname=asd)> but was:<User(firstName=null, lastname=aaa)>",
+                false
+            ),
+            Some(" -> User(.lastname -> [-asd-]{+aaa+})".to_string())
+        );
+    }
+
+    #[test]
+    fn report_hints() {
+        let xml = r#"<testsuite name="UserTest">
+  <testcase name="basic()" classname="com.example.UserTest" time="0.01">
+    <failure message="expected: &lt;User(firstName=null, lastname=asd)&gt; but was: &lt;User(firstName=null, lastname=aaa)&gt;" type="org.opentest4j.AssertionFailedError">org.opentest4j.AssertionFailedError: expected: &lt;User(firstName=null, lastname=asd)&gt; but was: &lt;User(firstName=null, lastname=aaa)&gt;
+	at com.example.UserTest.basic(UserTest.java:12)</failure>
+  </testcase>
+  <testcase name="npe()" classname="com.example.UserTest" time="0.01">
+    <failure message="Cannot invoke &quot;String.length()&quot;" type="java.lang.NullPointerException"/>
+  </testcase>
+  <testcase name="ok()" classname="com.example.UserTest" time="0.01"/>
+</testsuite>"#;
+
+        assert_eq!(
+            get_report_hints(xml, false, &DiffOptions::default(), Format::Line),
+            vec![ReportHint {
+                class_name: "com.example.UserTest".to_string(),
+                name: "basic()".to_string(),
+                hint: " -> User(.lastname -> [-asd-]{+aaa+})".to_string(),
+            }]
+        );
+        assert_eq!(
+            get_report_hints(xml, false, &DiffOptions::default(), Format::Json),
+            vec![ReportHint {
+                class_name: "com.example.UserTest".to_string(),
+                name: "basic()".to_string(),
+                hint: concat!(
+                    "{\"schema_version\":1,\"test\":\"com.example.UserTest.basic()\",\"equal\":false,\"ignored\":0,\"warnings\":[],\"changes\":[",
+                    "{\"path\":\"$.lastname\",\"kind\":\"changed\",\"expected\":\"asd\",\"actual\":\"aaa\"}",
+                    "]}"
+                )
+                .to_string(),
+            }]
         );
     }
 
@@ -99,4 +653,20 @@ mod tests {
             Some("List.of(\n  User.builder()\n        .name(\"first\")\n    .other(null)\n.build(),\n  User.builder()\n        .name(\"second\")\n    .other(null)\n.build()\n)".to_string())
         )
     }
+
+    #[test]
+    fn time_code() {
+        assert_eq!(
+            to_code("Slot(start=10:00+01:00, end=11:00, day=2024-01-01)"),
+            Some("Slot.builder()\n    .start(OffsetTime.of(LocalTime.parse(\"10:00\"), ZoneOffset.of(\"+01:00\")))\n  .end(LocalTime.parse(\"11:00\"))\n  .day(LocalDate.parse(\"2024-01-01\"))\n.build()".to_string())
+        );
+    }
+
+    #[test]
+    fn record_code() {
+        assert_eq!(
+            to_code("User[name=first, other=Other[id=1]]"),
+            Some("new User(\"first\", new Other(1))".to_string())
+        )
+    }
 }
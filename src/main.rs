@@ -1,4 +1,11 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+};
+
 use clap::Parser;
+use coffee_stain::{DiffOptions, Format, Granularity, Notation, Tolerance};
 
 #[derive(Parser, Debug, Clone)]
 #[clap(author, version, about, long_about = None)]
@@ -10,10 +17,108 @@ pub struct Args {
     /// Print code from string version
     #[clap(long)]
     pub code: bool,
+
+    /// Simplify every failed test of Surefire/Gradle XML reports. Directories are searched for TEST-*.xml
+    #[clap(long, num_args = 1.., value_name = "PATH")]
+    pub report: Vec<PathBuf>,
+
+    /// Simplify every assertion failure of a build log. Reads stdin when no file or "-" is given
+    #[clap(long, num_args = 0..=1, value_name = "FILE", default_missing_value = "-")]
+    pub scan: Option<PathBuf>,
+
+    /// Compare lists like sets, ignoring the order of their items
+    #[clap(long)]
+    pub unordered: bool,
+
+    /// Fields that identify the Dtos of a list, so they are compared with the Dto of the same identity
+    #[clap(long, num_args = 1.., value_name = "FIELD")]
+    pub identity: Vec<String>,
+
+    /// Fields that are never different like timestamps. A field name or a path like User.id or **.meta.createdAt
+    #[clap(long, num_args = 1.., value_name = "PATTERN")]
+    pub ignore: Vec<String>,
+
+    /// How far numbers and times may be off: 0.001, 1%, 5ms. A path in front of = limits it: price=0.01
+    #[clap(long, num_args = 1.., value_name = "TOLERANCE", value_parser = coffee_stain::parse_tolerance)]
+    pub tolerance: Vec<(String, Tolerance)>,
+
+    /// Compare strings by char, word or as a whole. auto picks one by their length and how much they differ
+    #[clap(long, value_name = "auto|char|word|whole")]
+    pub granularity: Option<Granularity>,
+
+    /// How hints are written. summary adds the amount of changes and leaves out deep or many changes,
+    /// tree writes one change per line, side-by-side both values next to each other
+    /// unified a unified diff of both values and json or jsonl the changes for other tools
+    #[clap(
+        long,
+        default_value = "line",
+        value_name = "line|summary|tree|side-by-side|unified|json|jsonl"
+    )]
+    pub format: Format,
+
+    /// Levels of Dtos and lists that the summary shows
+    #[clap(long, default_value_t = 3)]
+    pub depth: usize,
+
+    /// Changes of one Dto or list that the summary shows
+    #[clap(long, default_value_t = 10)]
+    pub max_changes: usize,
+
+    /// Unchanged fields next to a change that the tree shows
+    #[clap(long, default_value_t = 0)]
+    pub context: usize,
+
+    /// Characters that the side by side view fits into. Defaults to the width of the terminal, $COLUMNS or 120
+    #[clap(long)]
+    pub width: Option<usize>,
+
+    /// How the unified diff prints values
+    #[clap(long, default_value = "tostring", value_name = "tostring|json|yaml")]
+    pub notation: Notation,
+
+    /// Read ignore, identity, unordered, tolerance and granularity settings from a file of "key = value" lines
+    #[clap(long, value_name = "FILE")]
+    pub config: Option<PathBuf>,
 }
 
 fn main() {
     let args = Args::parse();
+    let mut options = DiffOptions::default();
+    if let Some(path) = &args.config {
+        let config = std::fs::read_to_string(path).map_err(|e| e.to_string());
+        if let Err(e) = config.and_then(|c| coffee_stain::read_config(&c, &mut options)) {
+            eprintln!("Unable to read {}: {e}", path.display());
+            std::process::exit(1);
+        }
+    }
+    options.unordered_lists |= args.unordered;
+    if !args.identity.is_empty() {
+        options.identity_fields = args.identity;
+    }
+    options.ignore.extend(args.ignore);
+    options.tolerances.extend(args.tolerance);
+    if let Some(granularity) = args.granularity {
+        options.granularity = granularity;
+    }
+    let format = match args.format {
+        Format::Summary { .. } => Format::Summary {
+            depth: args.depth,
+            changes: args.max_changes,
+        },
+        Format::Tree { .. } => Format::Tree {
+            context: args.context,
+        },
+        Format::SideBySide { .. } => Format::SideBySide {
+            width: args.width.unwrap_or_else(terminal_width),
+        },
+        Format::Unified { .. } => Format::Unified {
+            notation: args.notation,
+        },
+        format @ (Format::Line | Format::Json | Format::JsonLines) => format,
+    };
+
+    // Off when stdout is no terminal or NO_COLOR is set, so logs get the plain [-a-]{+b+} notation
+    let color = colored::control::SHOULD_COLORIZE.should_colorize();
 
     let stdin = std::io::stdin();
     let mut line = String::new();
@@ -22,7 +127,7 @@ fn main() {
         eprintln!("Please paste the line that includes: \n  - \"org.opentest4j.AssertionFailedError: expected: <*> but was: <*>\" \n");
 
         let _ = stdin.read_line(&mut line);
-        if let Some(message) = coffee_stain::get_hint(&line, true) {
+        if let Some(message) = coffee_stain::get_hint_with_format(&line, color, &options, format) {
             println!("{}", message);
         }
     }
@@ -36,4 +141,81 @@ fn main() {
             println!("{}", message);
         }
     }
+
+    for path in report_files(&args.report) {
+        match std::fs::read_to_string(&path) {
+            Ok(xml) => {
+                for hint in coffee_stain::get_report_hints(&xml, color, &options, format) {
+                    match format {
+                        // The test name is a field of the JSON
+                        Format::Json | Format::JsonLines => println!("{}", hint.hint),
+                        _ => println!("{}.{}{}", hint.class_name, hint.name, hint.hint),
+                    }
+                }
+            }
+            Err(e) => eprintln!("Unable to read {}: {e}", path.display()),
+        }
+    }
+
+    if let Some(path) = args.scan {
+        if let Err(e) = scan(&path, options, format, color) {
+            eprintln!("Unable to read {}: {e}", path.display());
+        }
+    }
+}
+
+fn scan(path: &Path, options: DiffOptions, format: Format, color: bool) -> std::io::Result<()> {
+    let reader: Box<dyn BufRead> = if path == Path::new("-") {
+        Box::new(std::io::stdin().lock())
+    } else {
+        Box::new(BufReader::new(File::open(path)?))
+    };
+
+    for hint in coffee_stain::scan_hints(reader, color, options, format) {
+        let hint = hint?;
+        match format {
+            Format::Json | Format::JsonLines => println!("{}", hint.hint),
+            _ => println!("{}:{}", hint.line, hint.hint),
+        }
+    }
+    Ok(())
+}
+
+/// Columns of the terminal that stdout writes to, $COLUMNS when it is not a terminal or 120
+fn terminal_width() -> usize {
+    match terminal_size::terminal_size() {
+        Some((terminal_size::Width(width), _)) => width as usize,
+        None => std::env::var("COLUMNS")
+            .ok()
+            .and_then(|c| c.parse().ok())
+            .unwrap_or(120),
+    }
+}
+
+/// Replaces directories with the TEST-*.xml files they contain
+fn report_files(paths: &[PathBuf]) -> Vec<PathBuf> {
+    let mut out = vec![];
+    for path in paths {
+        if !path.is_dir() {
+            out.push(path.clone());
+            continue;
+        }
+        match std::fs::read_dir(path) {
+            Ok(entries) => {
+                let mut files: Vec<PathBuf> = entries
+                    .filter_map(|e| e.ok())
+                    .map(|e| e.path())
+                    .filter(|p| {
+                        p.file_name()
+                            .and_then(|n| n.to_str())
+                            .is_some_and(|n| n.starts_with("TEST-") && n.ends_with(".xml"))
+                    })
+                    .collect();
+                files.sort();
+                out.extend(files);
+            }
+            Err(e) => eprintln!("Unable to read {}: {e}", path.display()),
+        }
+    }
+    out
 }
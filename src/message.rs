@@ -2,23 +2,37 @@ use colored::Colorize;
 use itertools::Itertools;
 
 use crate::{
-    diff::Difference,
+    diff::{is_invisible, type_name, Difference, Subtlety},
     parser::{DtoField, ValueKind},
 };
 
+/// Note about a part of the failure message that may have been parsed wrong
+pub fn warning(warning: &str, color: bool) -> String {
+    match color {
+        true => format!(" ({})", format!("warning: {warning}").yellow()),
+        false => format!(" (warning: {warning})"),
+    }
+}
+
 pub fn message(diff: Difference, color: bool) -> String {
     let mut out = String::new();
 
     out = match diff {
         Difference::Equal => out,
-        Difference::Type(a, b) if color => format!(
-            "{out} \"{}\" and \"{}\" are not the same Type",
-            a.yellow(),
-            b.yellow()
+        Difference::Type((a, b)) if color => format!(
+            "{out} \"{}\" and \"{}\" are not the same Type ({} -> {})",
+            type_name(&a).yellow(),
+            type_name(&b).yellow(),
+            message_value(Some(a), color),
+            highlight(&message_value(Some(b), color), color)
+        ),
+        Difference::Type((a, b)) => format!(
+            "{out} \"{}\" and \"{}\" are not the same Type ({} -> {})",
+            type_name(&a),
+            type_name(&b),
+            message_value(Some(a), color),
+            message_value(Some(b), color)
         ),
-        Difference::Type(a, b) => {
-            format!("{out} \"{a}\" and \"{b}\" are not the same Type")
-        }
         Difference::Child(child) => format!(
             "{out} -> {}",
             child.into_iter().map(|c| message(c, color)).join("")
@@ -33,10 +47,9 @@ pub fn message(diff: Difference, color: bool) -> String {
         ),
         Difference::CharsEqual(s) => format!("{out}{s}"),
         Difference::CharsRemove(s) if color => format!("{out}{}", s.red()),
-        Difference::CharsRemove(_) => out,
+        Difference::CharsRemove(s) => format!("{out}[-{s}-]"),
         Difference::CharsAdd(s) if color => format!("{out}{}", s.green()),
-        Difference::CharsAdd(s) if !color => format!("{out}{}", s),
-        Difference::CharsAdd(_) => out,
+        Difference::CharsAdd(s) => format!("{out}{{+{s}+}}"),
         Difference::UndefinedLeft(v) => format!("{out} additional {}", message_value(v, color)),
         Difference::UndefinedRight(v) => format!("{out} missing {}", message_value(v, color)),
         Difference::ClassChange(diff) => format!("{out}{}", message(*diff, color)),
@@ -46,15 +59,94 @@ pub fn message(diff: Difference, color: bool) -> String {
         Difference::FieldValueChange((name, diff)) => {
             format!("{out}.{name}{}", message(*diff, color))
         }
+        Difference::ParentChange(diff) => format!("{out}.super{}", message(*diff, color)),
+        Difference::NumberChange((real, delta)) => {
+            format!("{out} -> {} (off by {delta})", highlight(&real, color))
+        }
+        Difference::PrecisionChange((expected, real)) => format!(
+            "{out} -> {} (only the precision differs from {expected})",
+            highlight(&real, color)
+        ),
+        Difference::TimezoneChange((expected, real)) => format!(
+            "{out} -> {} (only the timezone differs from {expected})",
+            highlight(&real, color)
+        ),
+        Difference::SubtleChange((expected, real, Subtlety::Whitespace)) => format!(
+            "{out} -> {} (only whitespace differs from {})",
+            highlight(&visible(&real, false), color),
+            visible(&expected, false)
+        ),
+        Difference::SubtleChange((expected, real, Subtlety::Case)) => format!(
+            "{out} -> {} (only the case differs from {expected})",
+            highlight(&real, color)
+        ),
+        Difference::SubtleChange((expected, real, Subtlety::Unicode)) => format!(
+            "{out} -> {} (only the Unicode normalization differs from {})",
+            highlight(&visible(&real, true), color),
+            visible(&expected, true)
+        ),
+        Difference::MissingField(field) => {
+            format!("{out} missing field {}", message_field(field, color))
+        }
+        Difference::ExtraField(field) => {
+            format!("{out} extra field {}", message_field(field, color))
+        }
+        Difference::Inserted((j, v)) => {
+            format!("{out} inserted at [{j}] {}", message_value(Some(v), color))
+        }
+        Difference::Removed((i, v)) => {
+            format!("{out} removed at [{i}] {}", message_value(Some(v), color))
+        }
+        Difference::Moved((i, j)) => format!("{out} moved [{i}] -> [{j}]"),
+        Difference::Changed((i, _, None, diff)) => {
+            format!("{out} changed at [{i}]{}", message(*diff, color))
+        }
+        Difference::Changed((i, _, Some(id), diff)) => {
+            format!("{out} changed at [{i}] {id}{}", message(*diff, color))
+        }
+        Difference::FormatChange((expected, real)) => format!(
+            "{out} -> {} (same value as {expected})",
+            highlight(&real, color)
+        ),
     };
 
     out
 }
 
-fn message_value(v: Option<ValueKind<'_>>, color: bool) -> String {
+/// Replaces invisible characters with symbols: a␠b⏎, \u00a0.
+/// Other characters that are not ASCII are escaped with `escape`
+fn visible(text: &str, escape: bool) -> String {
+    text.chars()
+        .map(|c| match c {
+            ' ' => "\u{2420}".to_string(),
+            '\t' => "\u{2409}".to_string(),
+            '\r' => "\u{240d}".to_string(),
+            '\n' => "\u{23ce}".to_string(),
+            c if is_invisible(c) || (escape && !c.is_ascii()) => format!("\\u{:04x}", c as u32),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+fn highlight(real: &str, color: bool) -> String {
+    match color {
+        true => real.green().to_string(),
+        false => real.to_string(),
+    }
+}
+
+pub fn message_value(v: Option<ValueKind<'_>>, color: bool) -> String {
     match v {
         Some(ValueKind::Null) => "null".to_string(),
-        Some(ValueKind::String(s)) => format!("\"{s}\""),
+        Some(ValueKind::String(s) | ValueKind::Quoted(s)) => format!("\"{s}\""),
+        Some(
+            ValueKind::Integer(s)
+            | ValueKind::Decimal(s)
+            | ValueKind::Boolean(s)
+            | ValueKind::Constant(s)
+            | ValueKind::Uuid(s)
+            | ValueKind::DateTime(s),
+        ) => s.to_string(),
         Some(ValueKind::Map(m)) => m
             .into_iter()
             .map(|v| message_value(Some(v), color))
@@ -63,13 +155,27 @@ fn message_value(v: Option<ValueKind<'_>>, color: bool) -> String {
             .into_iter()
             .map(|v| message_value(Some(v), color))
             .join(","),
+        Some(ValueKind::Tuple(t)) => format!(
+            "({})",
+            t.into_iter()
+                .map(|v| message_value(Some(v), color))
+                .join(",")
+        ),
         Some(ValueKind::Dto(dto)) => {
-            let fields = dto
-                .fields
+            let parent = dto
+                .parent
+                .map(|p| format!("super={}", message_value(Some(*p), color)));
+            let fields = parent
                 .into_iter()
-                .map(|v| message_field(v, color))
+                .chain(dto.fields.into_iter().map(|v| message_field(v, color)))
                 .join(",");
-            format!("{}({})", dto.name, fields)
+            format!(
+                "{}{}{}{}",
+                dto.name,
+                dto.style.open(),
+                fields,
+                dto.style.close()
+            )
         }
         Some(ValueKind::Field(field)) => message_field(*field, color),
         None => String::new(),
@@ -77,5 +183,8 @@ fn message_value(v: Option<ValueKind<'_>>, color: bool) -> String {
 }
 
 fn message_field(field: DtoField<'_>, color: bool) -> String {
+    if field.is_positional() {
+        return message_value(Some(field.value), color);
+    }
     format!("{}={}", field.name, message_value(Some(field.value), color))
 }
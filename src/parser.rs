@@ -1,125 +1,749 @@
+use std::borrow::Cow;
+
 use nom::branch::alt;
-use nom::bytes::complete::{take_till, take_while};
-use nom::character::complete::alpha0;
-use nom::multi::separated_list0;
-use nom::sequence::{delimited, separated_pair};
+use nom::bytes::complete::take_while1;
+use nom::error::{Error, ErrorKind};
+use nom::multi::{separated_list0, separated_list1};
+use nom::sequence::{delimited, preceded, separated_pair, terminated};
 use nom::{
     bytes::complete::{tag, take_until},
     character::complete::{char, multispace0},
-    combinator::opt,
+    combinator::{all_consuming, map, opt, peek},
     sequence::pair,
     IResult,
 };
 
+use crate::{assertj, hamcrest, junit4};
+
 // https://github.com/rust-bakery/nom
 #[derive(Debug, PartialEq)]
 pub struct AssertionFailedError<'a> {
     pub expected: ValueKind<'a>,
     pub real: ValueKind<'a>,
+    /// Notes about ambiguous parts of the message that may have been parsed wrong
+    pub warnings: Vec<String>,
 }
 
-#[derive(Debug, PartialEq, Hash, Eq)]
+#[derive(Debug, PartialEq, Hash, Eq, Clone)]
 pub enum ValueKind<'a> {
     Null,
-    String(&'a str),
+    String(Cow<'a, str>),
+    /// "Hello" the text without the quotes
+    Quoted(Cow<'a, str>),
+    /// 500, -1
+    Integer(Cow<'a, str>),
+    /// 500.0, 1.5E3
+    Decimal(Cow<'a, str>),
+    /// true, FALSE
+    Boolean(Cow<'a, str>),
+    /// Enum constants: ACTIVE, IN_PROGRESS
+    Constant(Cow<'a, str>),
+    /// 123e4567-e89b-12d3-a456-426614174000
+    Uuid(Cow<'a, str>),
+    /// ISO dates and times: 2024-01-01, 12:30, 2024-01-01T00:00:00+01:00[Europe/Berlin]
+    DateTime(Cow<'a, str>),
     Array(Vec<ValueKind<'a>>),
     Map(Vec<ValueKind<'a>>),
+    /// Kotlin Pair and Triple: (a, b)
+    Tuple(Vec<ValueKind<'a>>),
     Dto(Dto<'a>),             // NEW
     Field(Box<DtoField<'a>>), // NEW
 }
 
-#[derive(Debug, PartialEq, Hash, Eq)]
+impl ValueKind<'_> {
+    /// The text of values without structure
+    pub fn scalar_text(&self) -> Option<&str> {
+        match self {
+            ValueKind::String(s)
+            | ValueKind::Quoted(s)
+            | ValueKind::Integer(s)
+            | ValueKind::Decimal(s)
+            | ValueKind::Boolean(s)
+            | ValueKind::Constant(s)
+            | ValueKind::Uuid(s)
+            | ValueKind::DateTime(s) => Some(s),
+            ValueKind::Null
+            | ValueKind::Array(_)
+            | ValueKind::Map(_)
+            | ValueKind::Tuple(_)
+            | ValueKind::Dto(_)
+            | ValueKind::Field(_) => None,
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Hash, Eq, Clone)]
 pub struct Dto<'a> {
     pub name: &'a str,
+    /// Lombok callSuper: Child(super=Parent(id=1), name=x)
+    pub parent: Option<Box<ValueKind<'a>>>,
     pub fields: Vec<DtoField<'a>>,
+    pub style: DtoStyle,
+}
+
+/// The brackets around the fields of a Dto
+#[derive(Debug, Default, PartialEq, Hash, Eq, Clone, Copy)]
+pub enum DtoStyle {
+    /// Lombok and Kotlin: User(name=first)
+    #[default]
+    Parentheses,
+    /// Java records: User[name=first]
+    Brackets,
 }
 
-#[derive(Debug, PartialEq, Hash, Eq)]
+impl DtoStyle {
+    pub fn open(self) -> char {
+        match self {
+            DtoStyle::Parentheses => '(',
+            DtoStyle::Brackets => '[',
+        }
+    }
+
+    pub fn close(self) -> char {
+        match self {
+            DtoStyle::Parentheses => ')',
+            DtoStyle::Brackets => ']',
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Hash, Eq, Clone)]
 pub struct DtoField<'a> {
-    pub name: &'a str,
+    pub name: Cow<'a, str>,
     pub value: ValueKind<'a>,
 }
 
+impl DtoField<'_> {
+    /// Lombok with includeFieldNames=false only prints the values: User(first, null).
+    /// These fields are named by their index, which no Java field name can start with
+    pub fn is_positional(&self) -> bool {
+        self.name.starts_with(|c: char| c.is_ascii_digit())
+    }
+}
+
+/// Where a value is parsed. Decides where a value without structure ends
+#[derive(Debug, Clone, Copy)]
+struct Context {
+    /// The bracket that closes the surrounding structure
+    closer: Option<char>,
+    /// Values of Dto fields only end at a "," that is followed by the next field
+    field: bool,
+    /// Dtos without field names like User(first, null) are only read where no field names were
+    /// printed around them. In a named field "call=f(x, y)" is more likely text
+    positional: bool,
+}
+
+const TOP_LEVEL: Context = Context {
+    closer: None,
+    field: false,
+    positional: true,
+};
+
+impl Context {
+    /// Elements of a list, map or tuple inside of this context
+    fn list(self, closer: char) -> Self {
+        Self {
+            closer: Some(closer),
+            field: false,
+            positional: self.positional,
+        }
+    }
+
+    fn field(closer: char) -> Self {
+        Self {
+            closer: Some(closer),
+            field: true,
+            positional: false,
+        }
+    }
+}
+
 /// Parses the last step the value
 /// demo input: null
-fn parse_field_string_value(input: &str) -> IResult<&str, ValueKind<'_>> {
-    let (input, value) =
-        take_till(|c: char| c == ',' || c == ')' || c == ']' || c == '}' || c == '>')(input)?;
-    // Assuming the next character is either ',' or ')', consume it without assigning.
-    match value {
-        "null" => Ok((input, ValueKind::Null)),
-        _ => Ok((input, ValueKind::String(value))),
+/// Balanced brackets are part of the value so "f(a, b)" and "List<String>" stay together
+fn parse_field_string_value(input: &str, context: Context) -> IResult<&str, ValueKind<'_>> {
+    let mut depth = 0;
+    let mut end = input.len();
+    for (i, c) in input.char_indices() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' if depth > 0 => depth -= 1,
+            c if depth == 0 && Some(c) == context.closer => {
+                end = i;
+                break;
+            }
+            ',' if depth == 0 && is_value_end(&input[i..], context) => {
+                end = i;
+                break;
+            }
+            _ => {}
+        }
     }
+    if depth > 0 {
+        // The value is not finished yet or its brackets are not balanced
+        return Err(nom::Err::Error(Error::new(input, ErrorKind::Verify)));
+    }
+    let (value, input) = input.split_at(end);
+    Ok((input, scalar(value.into())))
 }
 
-fn parse_array(input: &str) -> IResult<&str, ValueKind<'_>> {
-    let (input, values) = separated_list0(pair(char(','), multispace0), parse_value_kind)(input)?;
+/// Gives a value without structure its type by the way it is written
+/// demo input: null, 500, 1.5, true, ACTIVE, "Hello", 2024-01-01T00:00Z
+pub fn scalar(value: Cow<'_, str>) -> ValueKind<'_> {
+    let text: &str = &value;
+    if text == "null" {
+        ValueKind::Null
+    } else if text.len() >= 2 && text.starts_with('"') && text.ends_with('"') {
+        ValueKind::Quoted(match value {
+            Cow::Borrowed(s) => Cow::Borrowed(&s[1..s.len() - 1]),
+            Cow::Owned(s) => Cow::Owned(s[1..s.len() - 1].to_string()),
+        })
+    } else if is_integer(text) {
+        ValueKind::Integer(value)
+    } else if is_decimal(text) {
+        ValueKind::Decimal(value)
+    } else if text.eq_ignore_ascii_case("true") || text.eq_ignore_ascii_case("false") {
+        ValueKind::Boolean(value)
+    } else if is_uuid(text) {
+        ValueKind::Uuid(value)
+    } else if is_date_time(text) {
+        ValueKind::DateTime(value)
+    } else if is_constant(text) {
+        ValueKind::Constant(value)
+    } else {
+        ValueKind::String(value)
+    }
+}
 
-    Ok((input, ValueKind::Array(values)))
+fn is_digits(text: &str) -> bool {
+    !text.is_empty() && text.bytes().all(|b| b.is_ascii_digit())
 }
 
-fn parse_map(input: &str) -> IResult<&str, ValueKind<'_>> {
-    let (input, values) = separated_list0(pair(char(','), multispace0), parse_value_kind)(input)?;
+/// demo input: -12
+fn is_integer(text: &str) -> bool {
+    is_digits(text.strip_prefix('-').unwrap_or(text))
+}
 
-    Ok((input, ValueKind::Map(values)))
+/// demo input: -1.5E3
+fn is_decimal(text: &str) -> bool {
+    let text = text.strip_prefix('-').unwrap_or(text);
+    let (mantissa, exponent) = match text.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+        None => (text, None),
+    };
+    let mantissa = match mantissa.split_once('.') {
+        Some((int, fraction)) => is_digits(int) && is_digits(fraction),
+        None => exponent.is_some() && is_digits(mantissa),
+    };
+    mantissa && exponent.is_none_or(|e| is_digits(e.strip_prefix(['-', '+']).unwrap_or(e)))
 }
 
-fn parse_field_value_kind(input: &str) -> IResult<&str, ValueKind<'_>> {
-    let (input, (name, value)) = separated_pair(alpha0, tag("="), parse_value_kind)(input)?;
+/// demo input: 123e4567-e89b-12d3-a456-426614174000
+fn is_uuid(text: &str) -> bool {
+    let groups = text.split('-').map(str::len).collect::<Vec<_>>();
+    groups == [8, 4, 4, 4, 12] && text.chars().all(|c| c == '-' || c.is_ascii_hexdigit())
+}
 
-    Ok((input, ValueKind::Field(Box::new(DtoField { name, value }))))
+/// demo input: 2024-01-01T00:00:00.000+01:00[Europe/Berlin]
+fn is_date_time(text: &str) -> bool {
+    let (date, time) = match text.split_once('T') {
+        Some((date, time)) => (Some(date), Some(time)),
+        None if text.contains(':') => (None, Some(text)),
+        None => (Some(text), None),
+    };
+    let is_zoned_time = |time| {
+        let (time, zone) = split_zone(time);
+        is_time(time) && is_zone(zone)
+    };
+    // Only dates with a time have a named zone
+    date.is_none_or(is_date)
+        && time.is_none_or(is_zoned_time)
+        && (date.is_some() || !text.ends_with(']'))
+}
+
+/// demo input: 2024-01-01
+fn is_date(text: &str) -> bool {
+    let parts = text.split('-').collect::<Vec<_>>();
+    parts.len() == 3
+        && parts.iter().all(|p| is_digits(p))
+        && parts.iter().map(|p| p.len()).collect::<Vec<_>>() == [4, 2, 2]
+}
+
+/// demo input: 12:30:00.5
+fn is_time(text: &str) -> bool {
+    let parts = text.split(':').collect::<Vec<_>>();
+    let seconds = match parts.get(2) {
+        Some(seconds) => {
+            let (seconds, fraction) = seconds.split_once('.').unwrap_or((seconds, "0"));
+            seconds.len() == 2 && is_digits(seconds) && is_digits(fraction)
+        }
+        None => true,
+    };
+    (2..=3).contains(&parts.len())
+        && parts[..2].iter().all(|p| p.len() == 2 && is_digits(p))
+        && seconds
+}
+
+/// Splits a time into the local time and the offset or zone
+/// demo input: 00:00+01:00[Europe/Berlin]
+pub fn split_zone(time: &str) -> (&str, &str) {
+    let start = time.find(['Z', '+', '-', '[']).unwrap_or(time.len());
+    time.split_at(start)
+}
+
+/// demo input: +01:00[Europe/Berlin]
+fn is_zone(zone: &str) -> bool {
+    let offset = match zone.split_once('[') {
+        Some((offset, region)) if region.len() > 1 && region.ends_with(']') => offset,
+        Some(_) => return false,
+        None => zone,
+    };
+    matches!(offset, "" | "Z") || offset_seconds(offset).is_some()
+}
+
+/// Seconds of an offset from +HH to +HH:MM:SS, at most 18 hours like a java.time.ZoneOffset
+/// demo input: -05:30
+pub fn offset_seconds(offset: &str) -> Option<i64> {
+    let sign = match offset.get(..1)? {
+        "+" => 1,
+        "-" => -1,
+        _ => return None,
+    };
+    let offset = &offset[1..];
+    let digits = offset.replace(':', "");
+    // +HH:MM and +HHMM but not +H:MM
+    let grouped = !offset.contains(':') || offset.split(':').all(|p| p.len() == 2);
+    if !grouped || ![2, 4, 6].contains(&digits.len()) || !is_digits(&digits) {
+        return None;
+    }
+    let part = |i: usize| {
+        digits
+            .get(i..i + 2)
+            .map_or(Some(0), |p| p.parse::<i64>().ok())
+    };
+    let (hours, minutes, seconds) = (part(0)?, part(2)?, part(4)?);
+    let total = hours * 3600 + minutes * 60 + seconds;
+    (minutes < 60 && seconds < 60 && total <= 18 * 3600).then_some(sign * total)
+}
+
+/// demo input: IN_PROGRESS
+fn is_constant(text: &str) -> bool {
+    text.len() >= 2
+        && text.starts_with(|c: char| c.is_ascii_uppercase())
+        && text
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Checks if a value can end in front of the input
+fn is_value_end(input: &str, context: Context) -> bool {
+    let input = input.trim_start();
+    match input.chars().next() {
+        None => true,
+        Some(c) if Some(c) == context.closer => true,
+        Some(',') if context.field => is_field_start(&input[1..]),
+        Some(',') => context.closer.is_some(),
+        _ => false,
+    }
+}
+
+/// demo input:  lastname=asd
+fn is_field_start(input: &str) -> bool {
+    pair(multispace0, pair(parse_field_name, tag("=")))(input).is_ok()
+}
+
+/// A structured value is only taken when the value ends after it.
+/// Otherwise it is part of a longer value like "[draft] Hello"
+fn ended<'a>(
+    parser: impl Fn(&'a str) -> IResult<&'a str, ValueKind<'a>>,
+    context: Context,
+) -> impl Fn(&'a str) -> IResult<&'a str, ValueKind<'a>> {
+    move |input| {
+        let (rest, value) = parser(input)?;
+        if !is_value_end(rest, context) {
+            return Err(nom::Err::Error(Error::new(input, ErrorKind::Verify)));
+        }
+        Ok((rest, value))
+    }
+}
+
+fn separator(input: &str) -> IResult<&str, (char, &str)> {
+    pair(char(','), multispace0)(input)
+}
+
+fn parse_array(input: &str, context: Context) -> IResult<&str, ValueKind<'_>> {
+    map(|i| parse_items(i, '[', ']', context), ValueKind::Array)(input)
+}
+
+fn parse_map(input: &str, context: Context) -> IResult<&str, ValueKind<'_>> {
+    map(|i| parse_items(i, '{', '}', context), ValueKind::Map)(input)
+}
+
+fn parse_tuple(input: &str, context: Context) -> IResult<&str, ValueKind<'_>> {
+    map(|i| parse_items(i, '(', ')', context), ValueKind::Tuple)(input)
+}
+
+/// The values between the brackets. Empty brackets have no values, not an empty text
+/// demo input: [a, b]
+fn parse_items(
+    input: &str,
+    opener: char,
+    closer: char,
+    context: Context,
+) -> IResult<&str, Vec<ValueKind<'_>>> {
+    let context = context.list(closer);
+    preceded(
+        char(opener),
+        alt((
+            map(char(closer), |_| vec![]),
+            terminated(
+                separated_list1(separator, move |i| parse_value(i, context)),
+                char(closer),
+            ),
+        )),
+    )(input)
+}
+
+/// demo input: eee=as
+fn parse_field_value_kind(input: &str, context: Context) -> IResult<&str, ValueKind<'_>> {
+    let (input, (name, value)) =
+        separated_pair(parse_field_name, tag("="), |i| parse_value(i, context))(input)?;
+
+    Ok((
+        input,
+        ValueKind::Field(Box::new(DtoField {
+            name: name.into(),
+            value,
+        })),
+    ))
 }
 
 pub fn parse_value_kind(input: &str) -> IResult<&str, ValueKind<'_>> {
+    parse_value(input, TOP_LEVEL)
+}
+
+fn parse_value(input: &str, context: Context) -> IResult<&str, ValueKind<'_>> {
+    alt((
+        ended(move |i| parse_array(i, context), context),
+        ended(move |i| parse_map(i, context), context),
+        ended(move |i| parse_tuple(i, context), context),
+        ended(move |i| parse_field_value_kind(i, context), context),
+        ended(move |i| parse_dto(i, context), context),
+        move |i| parse_field_string_value(i, context),
+    ))(input)
+}
+
+/// Parses a value that was already cut out of a failure message.
+/// demo input: "Hello"
+/// Quoted strings are unquoted and values that are not structured are taken as they are.
+/// When the brackets of the value are not balanced the fields are split at their names.
+pub fn parse_whole_value<'a>(value: &'a str, warnings: &mut Vec<String>) -> Option<ValueKind<'a>> {
+    let value = parse_whole(value, warnings)?;
+    warn_positional(&value, warnings);
+    Some(value)
+}
+
+fn parse_whole<'a>(value: &'a str, warnings: &mut Vec<String>) -> Option<ValueKind<'a>> {
+    let value = value.trim();
+    if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+        return Some(scalar(value.into()));
+    }
+    let parsed = all_consuming(parse_value_kind)(value).map(|(_, value)| value);
+    match parsed {
+        // Structures that are only text as a whole may have unbalanced brackets
+        Ok(ValueKind::String(_)) if value.contains(['(', '[', '{']) => {}
+        Ok(value) => return Some(value),
+        Err(_) => {}
+    }
+    if let Some(value) = parse_unbalanced(value, warnings) {
+        return Some(value);
+    }
+    if let Ok(value) = parsed {
+        return Some(value);
+    }
+    if !value.contains(['(', '[', '{', '\n']) {
+        return Some(scalar(value.into()));
+    }
+    None
+}
+
+/// Field values like "call=f(x, y)" are read as text, though they could be Dtos without field names
+fn warn_positional(value: &ValueKind, warnings: &mut Vec<String>) {
+    match value {
+        ValueKind::Dto(dto) => {
+            for field in &dto.fields {
+                match &field.value {
+                    ValueKind::String(text)
+                        if all_consuming(|i| parse_dto(i, TOP_LEVEL))(text).is_ok() =>
+                    {
+                        let warning = format!(
+                            "{} of {} could be a Dto without field names, it was read as text",
+                            text, field.name
+                        );
+                        if !warnings.contains(&warning) {
+                            warnings.push(warning);
+                        }
+                    }
+                    value => warn_positional(value, warnings),
+                }
+            }
+            if let Some(parent) = &dto.parent {
+                warn_positional(parent, warnings);
+            }
+        }
+        ValueKind::Array(items) | ValueKind::Map(items) | ValueKind::Tuple(items) => {
+            for item in items {
+                warn_positional(item, warnings);
+            }
+        }
+        ValueKind::Field(field) => warn_positional(&field.value, warnings),
+        _ => {}
+    }
+}
+
+/// Fallback for values with unbalanced brackets like "User(text=:), b=1)".
+/// The fields are split where a field name follows and the brackets inside of them are ignored
+fn parse_unbalanced<'a>(value: &'a str, warnings: &mut Vec<String>) -> Option<ValueKind<'a>> {
+    let (name, body, style) = match value.strip_suffix(')') {
+        Some(rest) => {
+            let (name, body) = rest.split_once('(')?;
+            (name, body, DtoStyle::Parentheses)
+        }
+        None => {
+            let (name, body) = value.strip_suffix(']')?.split_once('[')?;
+            (name, body, DtoStyle::Brackets)
+        }
+    };
+    if name.is_empty() || !name.chars().all(is_class_name) {
+        return None;
+    }
+
+    let mut fields = vec![];
+    for field in split_fields(body) {
+        let (field_name, value) = field.split_once('=')?;
+        let value = match all_consuming(|i| parse_value(i, Context::field(style.close())))(value) {
+            Ok((_, value)) => value,
+            Err(_) => parse_unbalanced(value, warnings).unwrap_or_else(|| scalar(value.into())),
+        };
+        fields.push(DtoField {
+            name: field_name.into(),
+            value,
+        });
+    }
+    let warning =
+        format!("the brackets of {name} are not balanced, its fields were split at their names");
+    if !warnings.contains(&warning) {
+        warnings.push(warning);
+    }
+    Some(ValueKind::Dto(Dto {
+        name,
+        parent: None,
+        fields,
+        style,
+    }))
+}
+
+/// Splits at every "," that is followed by a field name
+fn split_fields(body: &str) -> Vec<&str> {
+    let mut fields = vec![];
+    let mut start = 0;
+    for (i, _) in body.match_indices(',') {
+        if is_field_start(&body[i + 1..]) {
+            fields.push(body[start..i].trim());
+            start = i + 1;
+        }
+    }
+    fields.push(body[start..].trim());
+    fields
+}
+
+/// demo input: user_id, $delegate, is valid, `is valid`
+fn parse_field_name(input: &str) -> IResult<&str, &str> {
     alt((
-        delimited(tag("["), parse_array, tag("]")),
-        delimited(tag("{"), parse_map, tag("}")),
-        parse_field_value_kind,
-        parse_dto,
-        parse_field_string_value,
+        delimited(char('`'), take_until("`"), char('`')),
+        take_while1(|c: char| is_identifier(c) || c == ' '),
     ))(input)
 }
 
 /// demo input: firstName=null
-fn parse_field(input: &str) -> IResult<&str, DtoField<'_>> {
-    let (input, (name, value)) = separated_pair(alpha0, tag("="), parse_value_kind)(input)?;
-    Ok((input, DtoField { name, value }))
+fn parse_field(input: &str, context: Context) -> IResult<&str, DtoField<'_>> {
+    let (input, (name, value)) =
+        separated_pair(parse_field_name, tag("="), |i| parse_value(i, context))(input)?;
+    Ok((
+        input,
+        DtoField {
+            name: name.into(),
+            value,
+        },
+    ))
 }
 
 /// demo input: firstName=null, lastname=asd
-fn parse_field_list(input: &str) -> IResult<&str, Vec<DtoField<'_>>> {
-    let (input, fields) = separated_list0(pair(char(','), multispace0), parse_field)(input)?;
+fn parse_field_list(input: &str, closer: char) -> IResult<&str, Vec<DtoField<'_>>> {
+    let context = Context::field(closer);
+    let (input, fields) = separated_list0(separator, |i| parse_field(i, context))(input)?;
     Ok((input, fields))
 }
 
-fn is_alphabetic(c: char) -> bool {
-    c.is_alphabetic()
+/// Characters of Java and Kotlin identifiers
+fn is_identifier(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
 }
 
+/// Nested classes are printed as Outer.Inner or Outer$Inner
+fn is_class_name(c: char) -> bool {
+    is_identifier(c) || c == '.'
+}
+
+/// demo input: first, null
+fn parse_positional_fields(input: &str, closer: char) -> IResult<&str, Vec<DtoField<'_>>> {
+    let context = TOP_LEVEL.list(closer);
+    let (input, values) = separated_list1(separator, |i| parse_value(i, context))(input)?;
+    let fields = values
+        .into_iter()
+        .enumerate()
+        .map(|(i, value)| DtoField {
+            name: i.to_string().into(),
+            value,
+        })
+        .collect();
+    Ok((input, fields))
+}
+
+/// demo input: super=Parent(id=1), name=x
+/// demo input: first, null
+fn parse_dto_body(input: &str, closer: char, context: Context) -> IResult<&str, DtoBody<'_>> {
+    let (input, parent) = opt(terminated(
+        preceded(tag("super="), |i| parse_value(i, context.list(closer))),
+        opt(separator),
+    ))(input)?;
+    let mut named = terminated(|i| parse_field_list(i, closer), peek(char(closer)));
+    let (input, fields) = match context.positional {
+        true => alt((named, |i| parse_positional_fields(i, closer)))(input)?,
+        false => named(input)?,
+    };
+    Ok((input, (parent.map(Box::new), fields)))
+}
+
+type DtoBody<'a> = (Option<Box<ValueKind<'a>>>, Vec<DtoField<'a>>);
+
 /// demo input: User(firstName=null, lastname=asd)
-fn parse_dto(input: &str) -> IResult<&str, ValueKind<'_>> {
-    let (input, name) = take_while(is_alphabetic)(input)?;
-    let (input, fields) = delimited(tag("("), parse_field_list, tag(")"))(input)?;
+/// demo input: User[firstName=null, lastname=asd]
+/// demo input: Outer.Inner(super=Parent(id=1), first, null)
+fn parse_dto(input: &str, context: Context) -> IResult<&str, ValueKind<'_>> {
+    let (input, name) = take_while1(is_class_name)(input)?;
+    let (input, ((parent, fields), style)) = alt((
+        map(
+            delimited(tag("("), |i| parse_dto_body(i, ')', context), tag(")")),
+            |body| (body, DtoStyle::Parentheses),
+        ),
+        map(
+            delimited(tag("["), |i| parse_dto_body(i, ']', context), tag("]")),
+            |body| (body, DtoStyle::Brackets),
+        ),
+    ))(input)?;
     let (input, _) = opt(multispace0)(input)?;
-    Ok((input, ValueKind::Dto(Dto { name, fields })))
+    Ok((
+        input,
+        ValueKind::Dto(Dto {
+            name,
+            parent,
+            fields,
+            style,
+        }),
+    ))
 }
 
-/// demo input: org.opentest4j.AssertionFailedError: expected: <User(firstName=null, lastname=asd)> but was: <User(firstName=null, lastname=aaa)>
+/// Parses the failure message of any supported assertion library
 pub fn parse(input: &str) -> IResult<&str, AssertionFailedError<'_>> {
+    alt((
+        parse_opentest4j,
+        assertj::parse,
+        hamcrest::parse,
+        junit4::parse,
+    ))(input)
+}
+
+/// demo input: org.opentest4j.AssertionFailedError: expected: <User(firstName=null, lastname=asd)> but was: <User(firstName=null, lastname=aaa)>
+/// The values can contain "> but was: <" and ">" so every split is tried until both values can be parsed
+fn parse_opentest4j(input: &str) -> IResult<&str, AssertionFailedError<'_>> {
     let (input, _) = take_until("expected: ")(input)?;
-    let (input, _) = tag("expected: ")(input)?;
-    let (input, expected) = delimited(tag("<"), parse_value_kind, tag(">"))(input)?;
-    let (input, _) = tag(" but was: ")(input)?;
-    let (input, real) = delimited(tag("<"), parse_value_kind, tag(">"))(input)?;
-    Ok((input, AssertionFailedError { expected, real }))
+    let (input, _) = tag("expected: <")(input)?;
+
+    // A split after the start of the next assertion failure belongs to that failure
+    let next = input.find("expected: <").unwrap_or(input.len());
+    let mut splits = input[..next]
+        .match_indices(BUT_WAS)
+        .filter_map(|(i, _)| split_opentest4j(&input[..i], &input[i + BUT_WAS.len()..]));
+    let Some((rest, mut error)) = splits.next() else {
+        return Err(nom::Err::Error(Error::new(input, ErrorKind::Verify)));
+    };
+    if splits.next().is_some() {
+        error.warnings.push(format!(
+            "the values contain \"{BUT_WAS}\", the first possible split was used"
+        ));
+    }
+    Ok((rest, error))
+}
+
+const BUT_WAS: &str = "> but was: <";
+
+/// How many ">" in the middle of a line are tried as the end of the real value.
+/// Every try looks through the rest of the input, so long log lines full of ">" would take too long
+const MAX_ENDS: usize = 64;
+
+/// Parses the expected value and finds the ">" that ends the real value.
+/// That is the first one that is followed by the end of the line or another assertion failure
+fn split_opentest4j<'a>(
+    expected: &'a str,
+    input: &'a str,
+) -> Option<(&'a str, AssertionFailedError<'a>)> {
+    let mut warnings = vec![];
+    let expected = parse_whole_value(expected, &mut warnings)?;
+
+    let ends = || input.match_indices('>').map(|(i, _)| i);
+    let is_value = |i: usize| parse_whole_value(&input[..i], &mut vec![]).is_some();
+    let mut tries = 0;
+    let end = ends()
+        .filter(|&i| {
+            let rest = &input[i + 1..];
+            let line = rest.trim_start_matches([' ', '\t', '\r']);
+            if line.is_empty() || line.starts_with('\n') {
+                return true;
+            }
+            tries += 1;
+            tries <= MAX_ENDS && is_failure_next(rest)
+        })
+        .take(MAX_ENDS)
+        .find(|&i| is_value(i))
+        .or_else(|| ends().take(MAX_ENDS).find(|&i| is_value(i)))?;
+    let real = parse_whole_value(&input[..end], &mut warnings)?;
+
+    Some((
+        &input[end + 1..],
+        AssertionFailedError {
+            expected,
+            real,
+            warnings,
+        },
+    ))
+}
+
+/// Checks if another assertion failure follows. Its values are not split, trying every split
+/// of every following failure would take exponential time for many failures on one line
+fn is_failure_next(input: &str) -> bool {
+    let opentest4j = input
+        .find("expected: <")
+        .is_some_and(|i| input[i..].contains(BUT_WAS));
+    opentest4j || alt((assertj::parse, hamcrest::parse, junit4::parse))(input).is_ok()
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
         parser::{
-            parse, parse_field_value_kind, parse_map, AssertionFailedError, Dto, DtoField,
-            ValueKind,
+            parse, parse_field_value_kind, parse_map, parse_value_kind, scalar,
+            AssertionFailedError, Dto, DtoField, DtoStyle, ValueKind, TOP_LEVEL,
         },
         test_data,
     };
@@ -129,8 +753,9 @@ mod tests {
     fn parse_string() {
         let input = "org.opentest4j.AssertionFailedError: expected: <1> but was: <2>";
         let expected = AssertionFailedError {
-            expected: ValueKind::String("1"),
-            real: ValueKind::String("2"),
+            expected: ValueKind::Integer("1".into()),
+            real: ValueKind::Integer("2".into()),
+            warnings: vec![],
         };
 
         assert_eq!(parse(input), Ok(("", expected)));
@@ -144,28 +769,31 @@ mod tests {
                 name: "User",
                 fields: vec![
                     DtoField {
-                        name: "firstName",
+                        name: "firstName".into(),
                         value: ValueKind::Null,
                     },
                     DtoField {
-                        name: "lastname",
-                        value: ValueKind::String("asd"),
+                        name: "lastname".into(),
+                        value: ValueKind::String("asd".into()),
                     },
                 ],
+                ..Default::default()
             }),
             real: ValueKind::Dto(Dto {
                 name: "User",
                 fields: vec![
                     DtoField {
-                        name: "firstName",
+                        name: "firstName".into(),
                         value: ValueKind::Null,
                     },
                     DtoField {
-                        name: "lastname",
-                        value: ValueKind::String("aaa"),
+                        name: "lastname".into(),
+                        value: ValueKind::String("aaa".into()),
                     },
                 ],
+                ..Default::default()
             }),
+            warnings: vec![],
         };
 
         assert_eq!(parse(input), Ok(("", expected)));
@@ -179,40 +807,44 @@ mod tests {
                 name: "User",
                 fields: vec![
                     DtoField {
-                        name: "name",
-                        value: ValueKind::String("1"),
+                        name: "name".into(),
+                        value: ValueKind::Integer("1".into()),
                     },
                     DtoField {
-                        name: "other",
+                        name: "other".into(),
                         value: ValueKind::Dto(Dto {
                             name: "User",
                             fields: vec![
                                 DtoField {
-                                    name: "name",
-                                    value: ValueKind::String("2"),
+                                    name: "name".into(),
+                                    value: ValueKind::Integer("2".into()),
                                 },
                                 DtoField {
-                                    name: "other",
+                                    name: "other".into(),
                                     value: ValueKind::Null,
                                 },
                             ],
+                            ..Default::default()
                         }),
                     },
                 ],
+                ..Default::default()
             }),
             real: ValueKind::Dto(Dto {
                 name: "User",
                 fields: vec![
                     DtoField {
-                        name: "name",
-                        value: ValueKind::String("1"),
+                        name: "name".into(),
+                        value: ValueKind::Integer("1".into()),
                     },
                     DtoField {
-                        name: "other",
+                        name: "other".into(),
                         value: ValueKind::Null,
                     },
                 ],
+                ..Default::default()
             }),
+            warnings: vec![],
         };
 
         assert_eq!(parse(input), Ok(("", expected)));
@@ -222,42 +854,292 @@ mod tests {
     fn parse_field_value_kind_test() {
         let input = "eee=as";
         let expected = ValueKind::Field(Box::new(DtoField {
-            name: "eee",
-            value: ValueKind::String("as"),
+            name: "eee".into(),
+            value: ValueKind::String("as".into()),
         }));
 
-        assert_eq!(parse_field_value_kind(input), Ok(("", expected)));
+        assert_eq!(parse_field_value_kind(input, TOP_LEVEL), Ok(("", expected)));
+    }
+
+    #[test]
+    fn parse_delimiters() {
+        let input = "Lookup(description=Hello, world, types=Map<String, Integer>, tags=[a, b], call=f(x, y))";
+        let expected = ValueKind::Dto(Dto {
+            name: "Lookup",
+            fields: vec![
+                DtoField {
+                    name: "description".into(),
+                    value: ValueKind::String("Hello, world".into()),
+                },
+                DtoField {
+                    name: "types".into(),
+                    value: ValueKind::String("Map<String, Integer>".into()),
+                },
+                DtoField {
+                    name: "tags".into(),
+                    value: ValueKind::Array(vec![
+                        ValueKind::String("a".into()),
+                        ValueKind::String("b".into()),
+                    ]),
+                },
+                DtoField {
+                    name: "call".into(),
+                    value: ValueKind::String("f(x, y)".into()),
+                },
+            ],
+            ..Default::default()
+        });
+
+        assert_eq!(parse_value_kind(input), Ok(("", expected)));
+    }
+
+    #[test]
+    fn parse_positional_in_field() {
+        let input = "expected: <Call(call=f(x, y))> but was: <Call(call=f(x, z))>";
+        let (_, error) = parse(input).unwrap();
+
+        assert_eq!(
+            error.real,
+            ValueKind::Dto(Dto {
+                name: "Call",
+                fields: vec![DtoField {
+                    name: "call".into(),
+                    value: ValueKind::String("f(x, z)".into()),
+                }],
+                ..Default::default()
+            })
+        );
+        assert_eq!(
+            error.warnings,
+            vec![
+                "f(x, y) of call could be a Dto without field names, it was read as text",
+                "f(x, z) of call could be a Dto without field names, it was read as text",
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_ambiguous_split() {
+        let input = "expected: <a> but was: <b> but was: <c>";
+        let expected = AssertionFailedError {
+            expected: ValueKind::String("a".into()),
+            real: ValueKind::String("b> but was: <c".into()),
+            warnings: vec![
+                "the values contain \"> but was: <\", the first possible split was used".into(),
+            ],
+        };
+
+        assert_eq!(parse(input), Ok(("", expected)));
+    }
+
+    #[test]
+    fn parse_many_ends() {
+        let value = "b>".repeat(64 * 1024);
+        let input = format!("expected: <a> but was: <{value}\n");
+        let (_, error) = parse(&input).unwrap();
+
+        assert_eq!(
+            error.real,
+            ValueKind::String(value[..value.len() - 1].into())
+        );
+    }
+
+    #[test]
+    fn parse_many_failures_on_a_line() {
+        let input = "expected: <a> but was: <User(a=b>c>d>e)> ".repeat(12);
+        let mut rest = input.as_str();
+        let mut reals = vec![];
+        while let Ok((next, error)) = parse(rest) {
+            reals.push(error.real);
+            rest = next;
+        }
+        assert_eq!(reals.len(), 12);
+        assert_eq!(reals[0], parse_value_kind("User(a=b>c>d>e)").unwrap().1);
     }
 
     #[test]
     fn parse_map_test() {
-        let input = "eee=Complicated(a=b, b=2, c=500, d=600)";
+        let input = "{eee=Complicated(a=b, b=2, c=500, d=600)}";
         let expected = ValueKind::Map(vec![ValueKind::Field(Box::new(DtoField {
-            name: "eee",
+            name: "eee".into(),
             value: ValueKind::Dto(Dto {
                 name: "Complicated",
                 fields: vec![
                     DtoField {
-                        name: "a",
-                        value: ValueKind::String("b"),
+                        name: "a".into(),
+                        value: ValueKind::String("b".into()),
                     },
                     DtoField {
-                        name: "b",
-                        value: ValueKind::String("2"),
+                        name: "b".into(),
+                        value: ValueKind::Integer("2".into()),
                     },
                     DtoField {
-                        name: "c",
-                        value: ValueKind::String("500"),
+                        name: "c".into(),
+                        value: ValueKind::Integer("500".into()),
                     },
                     DtoField {
-                        name: "d",
-                        value: ValueKind::String("600"),
+                        name: "d".into(),
+                        value: ValueKind::Integer("600".into()),
                     },
                 ],
+                ..Default::default()
             }),
         }))]);
 
-        assert_eq!(parse_map(input), Ok(("", expected)));
+        assert_eq!(parse_map(input, TOP_LEVEL), Ok(("", expected)));
+    }
+
+    #[test]
+    fn parse_kotlin_data_class() {
+        let input = "User(user_id=1, id2=two, $delegate=null, is valid=true, `full name`=John Smith, tags=[a, b], meta={k=v, 1=one}, pair=(a, (b, c)), status=ACTIVE)";
+        let field = |name: &'static str, value| DtoField {
+            name: name.into(),
+            value,
+        };
+        let string = |s: &'static str| ValueKind::String(s.into());
+        let entry = |name, value| ValueKind::Field(Box::new(field(name, value)));
+        let expected = ValueKind::Dto(Dto {
+            name: "User",
+            fields: vec![
+                field("user_id", ValueKind::Integer("1".into())),
+                field("id2", string("two")),
+                field("$delegate", ValueKind::Null),
+                field("is valid", ValueKind::Boolean("true".into())),
+                field("full name", string("John Smith")),
+                field("tags", ValueKind::Array(vec![string("a"), string("b")])),
+                field(
+                    "meta",
+                    ValueKind::Map(vec![entry("k", string("v")), entry("1", string("one"))]),
+                ),
+                field(
+                    "pair",
+                    ValueKind::Tuple(vec![
+                        string("a"),
+                        ValueKind::Tuple(vec![string("b"), string("c")]),
+                    ]),
+                ),
+                field("status", ValueKind::Constant("ACTIVE".into())),
+            ],
+            ..Default::default()
+        });
+
+        assert_eq!(parse_value_kind(input), Ok(("", expected)));
+    }
+
+    #[test]
+    fn parse_record() {
+        let input = "[User[name=first, other=null], User[name=second, other=Other[]]]";
+        let user = |name: &'static str, other| {
+            ValueKind::Dto(Dto {
+                name: "User",
+                fields: vec![
+                    DtoField {
+                        name: "name".into(),
+                        value: ValueKind::String(name.into()),
+                    },
+                    DtoField {
+                        name: "other".into(),
+                        value: other,
+                    },
+                ],
+                style: DtoStyle::Brackets,
+                ..Default::default()
+            })
+        };
+        let expected = ValueKind::Array(vec![
+            user("first", ValueKind::Null),
+            user(
+                "second",
+                ValueKind::Dto(Dto {
+                    name: "Other",
+                    fields: vec![],
+                    style: DtoStyle::Brackets,
+                    ..Default::default()
+                }),
+            ),
+        ]);
+
+        assert_eq!(parse_value_kind(input), Ok(("", expected)));
+    }
+
+    #[test]
+    fn parse_empty_containers() {
+        assert_eq!(parse_value_kind("[]"), Ok(("", ValueKind::Array(vec![]))));
+        assert_eq!(parse_value_kind("{}"), Ok(("", ValueKind::Map(vec![]))));
+        assert_eq!(
+            parse_value_kind("[[], {}]"),
+            Ok((
+                "",
+                ValueKind::Array(vec![ValueKind::Array(vec![]), ValueKind::Map(vec![])])
+            ))
+        );
+    }
+
+    #[test]
+    fn scalar_types() {
+        assert_eq!(scalar("-12".into()), ValueKind::Integer("-12".into()));
+        assert_eq!(scalar("1.5E3".into()), ValueKind::Decimal("1.5E3".into()));
+        assert_eq!(scalar("FALSE".into()), ValueKind::Boolean("FALSE".into()));
+        assert_eq!(
+            scalar("IN_PROGRESS".into()),
+            ValueKind::Constant("IN_PROGRESS".into())
+        );
+        assert_eq!(
+            scalar("123e4567-e89b-12d3-a456-426614174000".into()),
+            ValueKind::Uuid("123e4567-e89b-12d3-a456-426614174000".into())
+        );
+        assert_eq!(
+            scalar("2024-01-01T00:00+01:00[Europe/Berlin]".into()),
+            ValueKind::DateTime("2024-01-01T00:00+01:00[Europe/Berlin]".into())
+        );
+        assert_eq!(
+            scalar("10:00-05:30".into()),
+            ValueKind::DateTime("10:00-05:30".into())
+        );
+        assert_eq!(
+            scalar("2024-01-01T00:00:00+99:99".into()),
+            ValueKind::String("2024-01-01T00:00:00+99:99".into())
+        );
+        assert_eq!(
+            scalar("2024-01-01T00:00+19:00".into()),
+            ValueKind::String("2024-01-01T00:00+19:00".into())
+        );
+        assert_eq!(
+            scalar("10:00[Europe/Berlin]".into()),
+            ValueKind::String("10:00[Europe/Berlin]".into())
+        );
+        assert_eq!(scalar("\"a b\"".into()), ValueKind::Quoted("a b".into()));
+        assert_eq!(scalar("1.2.3".into()), ValueKind::String("1.2.3".into()));
+        assert_eq!(scalar("A".into()), ValueKind::String("A".into()));
+    }
+
+    #[test]
+    fn parse_lombok_variants() {
+        let input = "Outer.Child$1(super=Parent(id=1), first, null)";
+        let expected = ValueKind::Dto(Dto {
+            name: "Outer.Child$1",
+            parent: Some(Box::new(ValueKind::Dto(Dto {
+                name: "Parent",
+                fields: vec![DtoField {
+                    name: "id".into(),
+                    value: ValueKind::Integer("1".into()),
+                }],
+                ..Default::default()
+            }))),
+            fields: vec![
+                DtoField {
+                    name: "0".into(),
+                    value: ValueKind::String("first".into()),
+                },
+                DtoField {
+                    name: "1".into(),
+                    value: ValueKind::Null,
+                },
+            ],
+            ..Default::default()
+        });
+
+        assert_eq!(parse_value_kind(input), Ok(("", expected)));
     }
 
     #[test]
@@ -0,0 +1,472 @@
+use std::str::FromStr;
+
+use itertools::{EitherOrBoth, Itertools};
+
+use crate::{
+    change::{field_step, Change},
+    diff::longest_common_subsequence_by,
+    message::message_value,
+    parser::ValueKind,
+};
+
+/// How values are pretty printed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Notation {
+    /// Like the toString() of Lombok and Kotlin: User(name="a")
+    #[default]
+    ToString,
+    Json,
+    Yaml,
+}
+
+impl FromStr for Notation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "tostring" => Ok(Self::ToString),
+            "json" => Ok(Self::Json),
+            "yaml" => Ok(Self::Yaml),
+            s => Err(format!("expected tostring, json or yaml but got \"{s}\"")),
+        }
+    }
+}
+
+/// A line of a pretty printed value
+#[derive(Debug, Clone, PartialEq)]
+pub struct Line {
+    /// Path of the value that the line shows like the path of a [crate::Change]
+    pub path: String,
+    /// The line closes the brackets of the value
+    pub end: bool,
+    pub text: String,
+}
+
+impl Line {
+    fn new(path: &str, text: String) -> Self {
+        Self {
+            path: path.to_string(),
+            end: false,
+            text,
+        }
+    }
+
+    fn end(path: &str, text: String) -> Self {
+        Self {
+            path: path.to_string(),
+            end: true,
+            text,
+        }
+    }
+}
+
+/// Pretty prints a value with Dtos, maps and lists spread over lines indented by two spaces
+pub fn lines(value: &ValueKind, notation: Notation) -> Vec<Line> {
+    let mut out = vec![];
+    match notation {
+        Notation::ToString => to_string_lines(value, "$", "", 0, &mut out),
+        Notation::Json => json_lines(value, "$", "", 0, "", &mut out),
+        Notation::Yaml => yaml_lines(value, "$", 0, &mut out),
+    }
+    out
+}
+
+/// Lines of the expected and the actual value next to each other
+#[derive(Debug, PartialEq)]
+pub enum Row<'l> {
+    /// Lines that show the same. Their text only differs where the options allow it
+    Same(&'l Line, &'l Line),
+    /// A changed line or one that only one side has
+    Changed(Option<&'l Line>, Option<&'l Line>),
+}
+
+/// Aligns the lines of two values by their longest common subsequence. Lines are the same when
+/// their text is or when they show the same value and no change is at or above its path.
+/// The lines in between are paired in order
+pub fn rows<'l>(expected: &'l [Line], actual: &'l [Line], changes: &[Change]) -> Vec<Row<'l>> {
+    let changed = |line: &'l Line| {
+        let changed = changes.iter().any(|c| {
+            line.path
+                .strip_prefix(c.path.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with(['.', '[']))
+        });
+        (line, changed)
+    };
+    let left = expected.iter().map(changed).collect_vec();
+    let right = actual.iter().map(changed).collect_vec();
+    let same = |(a, a_changed): &(&Line, bool), (b, b_changed): &(&Line, bool)| {
+        a.text == b.text || (a.path == b.path && a.end == b.end && !a_changed && !b_changed)
+    };
+
+    let mut out = vec![];
+    let (mut i, mut j) = (0, 0);
+    for (i_end, j_end) in longest_common_subsequence_by(&left, &right, same)
+        .into_iter()
+        .chain([(left.len(), right.len())])
+    {
+        for pair in expected[i..i_end].iter().zip_longest(&actual[j..j_end]) {
+            out.push(match pair {
+                EitherOrBoth::Both(l, r) => Row::Changed(Some(l), Some(r)),
+                EitherOrBoth::Left(l) => Row::Changed(Some(l), None),
+                EitherOrBoth::Right(r) => Row::Changed(None, Some(r)),
+            });
+        }
+        if let (Some(l), Some(r)) = (expected.get(i_end), actual.get(j_end)) {
+            out.push(Row::Same(l, r));
+        }
+        (i, j) = (i_end + 1, j_end + 1);
+    }
+    out
+}
+
+fn to_string_lines(value: &ValueKind, path: &str, prefix: &str, depth: usize, out: &mut Vec<Line>) {
+    let indent = "  ".repeat(depth);
+    let step = |name: &str| field_step(Some(value), name);
+    let (open, children, close): (String, Vec<Entry>, char) = match value {
+        ValueKind::Dto(dto) if dto.parent.is_some() || !dto.fields.is_empty() => {
+            let parent = dto
+                .parent
+                .iter()
+                .map(|p| ("super=".to_string(), ".super".to_string(), p.as_ref()));
+            let fields = dto.fields.iter().map(|f| match f.is_positional() {
+                true => (String::new(), step(&f.name), &f.value),
+                false => (format!("{}=", f.name), step(&f.name), &f.value),
+            });
+            (
+                format!("{}{}", dto.name, dto.style.open()),
+                parent.chain(fields).collect(),
+                dto.style.close(),
+            )
+        }
+        ValueKind::Array(items) if !items.is_empty() => ("[".to_string(), unnamed(items), ']'),
+        ValueKind::Map(entries) if !entries.is_empty() => {
+            let entries = entries
+                .iter()
+                .enumerate()
+                .map(|(i, e)| match e {
+                    ValueKind::Field(f) => (String::new(), step(&f.name), e),
+                    e => (String::new(), format!("[{i}]"), e),
+                })
+                .collect();
+            ("{".to_string(), entries, '}')
+        }
+        ValueKind::Tuple(items) if !items.is_empty() => ("(".to_string(), unnamed(items), ')'),
+        ValueKind::Field(field) => {
+            let prefix = format!("{prefix}{}=", field.name);
+            return to_string_lines(&field.value, path, &prefix, depth, out);
+        }
+        ValueKind::Array(_) => return out.push(Line::new(path, format!("{indent}{prefix}[]"))),
+        ValueKind::Map(_) => return out.push(Line::new(path, format!("{indent}{prefix}{{}}"))),
+        ValueKind::Tuple(_) => return out.push(Line::new(path, format!("{indent}{prefix}()"))),
+        ValueKind::String(text) | ValueKind::Quoted(text) => {
+            return out.push(Line::new(path, format!("{indent}{prefix}{}", quote(text))));
+        }
+        value => {
+            let value = message_value(Some(value.clone()), false);
+            return out.push(Line::new(path, format!("{indent}{prefix}{value}")));
+        }
+    };
+    out.push(Line::new(path, format!("{indent}{prefix}{open}")));
+    for (prefix, step, child) in children {
+        to_string_lines(child, &format!("{path}{step}"), &prefix, depth + 1, out);
+    }
+    out.push(Line::end(path, format!("{indent}{close}")));
+}
+
+fn json_lines(
+    value: &ValueKind,
+    path: &str,
+    prefix: &str,
+    depth: usize,
+    comma: &str,
+    out: &mut Vec<Line>,
+) {
+    let indent = "  ".repeat(depth);
+    let (open, children, close) = match (entries(value), items(value)) {
+        (Some(entries), _) if !entries.is_empty() => ('{', entries, '}'),
+        (_, Some(items)) if !items.is_empty() => ('[', unnamed(items), ']'),
+        _ => {
+            let text = format!("{indent}{prefix}{}{comma}", inline(value));
+            return out.push(Line::new(path, text));
+        }
+    };
+    out.push(Line::new(path, format!("{indent}{prefix}{open}")));
+    let last = children.len().saturating_sub(1);
+    for (i, (key, step, child)) in children.into_iter().enumerate() {
+        let prefix = match open {
+            '{' => format!("{}: ", quote(&key)),
+            _ => String::new(),
+        };
+        let comma = if i == last { "" } else { "," };
+        json_lines(
+            child,
+            &format!("{path}{step}"),
+            &prefix,
+            depth + 1,
+            comma,
+            out,
+        );
+    }
+    out.push(Line::end(path, format!("{indent}{close}{comma}")));
+}
+
+fn yaml_lines(value: &ValueKind, path: &str, depth: usize, out: &mut Vec<Line>) {
+    let indent = "  ".repeat(depth);
+    if let Some(entries) = entries(value).filter(|e| !e.is_empty()) {
+        for (key, step, child) in entries {
+            let path = format!("{path}{step}");
+            let key = match key
+                .chars()
+                .all(|c| c.is_alphanumeric() || "_.-".contains(c))
+            {
+                true => key,
+                false => quote(&key),
+            };
+            match is_block(child) {
+                true => {
+                    out.push(Line::new(&path, format!("{indent}{key}:")));
+                    yaml_lines(child, &path, depth + 1, out);
+                }
+                false => out.push(Line::new(
+                    &path,
+                    format!("{indent}{key}: {}", inline(child)),
+                )),
+            }
+        }
+    } else if let Some(items) = items(value).filter(|i| !i.is_empty()) {
+        for (i, child) in items.iter().enumerate() {
+            let path = format!("{path}[{i}]");
+            match is_block(child) {
+                true => {
+                    // The first line of the block starts behind the dash
+                    let start = out.len();
+                    yaml_lines(child, &path, depth + 1, out);
+                    out[start].text = format!("{indent}- {}", out[start].text.trim_start());
+                }
+                false => out.push(Line::new(&path, format!("{indent}- {}", inline(child)))),
+            }
+        }
+    } else {
+        out.push(Line::new(path, format!("{indent}{}", inline(value))));
+    }
+}
+
+/// A value as JSON on one line. Dtos start with their class name in `$class`
+pub fn json(value: &ValueKind) -> String {
+    let class = match value {
+        ValueKind::Dto(dto) => Some(format!("\"$class\":{}", quote(dto.name))),
+        _ => None,
+    };
+    match (entries(value), items(value)) {
+        (Some(entries), _) if !entries.is_empty() || class.is_some() => format!(
+            "{{{}}}",
+            class
+                .into_iter()
+                .chain(entries.into_iter().map(|(key, _, child)| format!(
+                    "{}:{}",
+                    quote(&key),
+                    json(child)
+                )))
+                .join(",")
+        ),
+        (_, Some(items)) if !items.is_empty() => format!("[{}]", items.iter().map(json).join(",")),
+        _ => inline(value),
+    }
+}
+
+/// Named values of Dtos and maps with the step of their path
+fn entries<'v, 'a>(value: &'v ValueKind<'a>) -> Option<Vec<Entry<'v, 'a>>> {
+    let step = |name: &str| field_step(Some(value), name);
+    match value {
+        ValueKind::Dto(dto) => Some(
+            dto.parent
+                .iter()
+                .map(|p| ("$super".to_string(), ".super".to_string(), p.as_ref()))
+                .chain(
+                    dto.fields
+                        .iter()
+                        .map(|f| (f.name.to_string(), step(&f.name), &f.value)),
+                )
+                .collect(),
+        ),
+        ValueKind::Map(entries) if !entries.is_empty() => Some(
+            entries
+                .iter()
+                .enumerate()
+                .map(|(i, e)| match e {
+                    ValueKind::Field(f) => (f.name.to_string(), step(&f.name), &f.value),
+                    e => (message_value(Some(e.clone()), false), format!("[{i}]"), e),
+                })
+                .collect(),
+        ),
+        ValueKind::Field(field) => Some(vec![(
+            field.name.to_string(),
+            step(&field.name),
+            &field.value,
+        )]),
+        _ => None,
+    }
+}
+
+/// Key, step of the path and value of an entry. The key of toString lines is written in front
+type Entry<'v, 'a> = (String, String, &'v ValueKind<'a>);
+
+fn items<'v, 'a>(value: &'v ValueKind<'a>) -> Option<&'v [ValueKind<'a>]> {
+    match value {
+        ValueKind::Array(items) | ValueKind::Tuple(items) => Some(items),
+        ValueKind::Map(items) => Some(items),
+        _ => None,
+    }
+}
+
+fn is_block(value: &ValueKind) -> bool {
+    entries(value).is_some_and(|e| !e.is_empty()) || items(value).is_some_and(|i| !i.is_empty())
+}
+
+/// Scalars and empty Dtos, maps and lists as JSON, which YAML can read as well
+fn inline(value: &ValueKind) -> String {
+    match value {
+        ValueKind::Null => "null".to_string(),
+        ValueKind::Integer(s) | ValueKind::Decimal(s) if is_json_number(s) => s.to_string(),
+        ValueKind::Boolean(s) => s.to_lowercase(),
+        ValueKind::Integer(s)
+        | ValueKind::Decimal(s)
+        | ValueKind::String(s)
+        | ValueKind::Quoted(s)
+        | ValueKind::Constant(s)
+        | ValueKind::Uuid(s)
+        | ValueKind::DateTime(s) => quote(s),
+        ValueKind::Array(_) | ValueKind::Tuple(_) => "[]".to_string(),
+        ValueKind::Dto(_) | ValueKind::Map(_) | ValueKind::Field(_) => "{}".to_string(),
+    }
+}
+
+/// Numbers like 01234 or 1.5E3 that JSON does not allow are written as strings
+/// demo input: -0.5e+3
+fn is_json_number(text: &str) -> bool {
+    let text = text.strip_prefix('-').unwrap_or(text);
+    let (mantissa, exponent) = match text.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+        None => (text, None),
+    };
+    let (int, fraction) = match mantissa.split_once('.') {
+        Some((int, fraction)) => (int, Some(fraction)),
+        None => (mantissa, None),
+    };
+    let digits = |d: &str| !d.is_empty() && d.bytes().all(|b| b.is_ascii_digit());
+    digits(int)
+        && (int == "0" || !int.starts_with('0'))
+        && fraction.is_none_or(digits)
+        && exponent.is_none_or(|e| digits(e.strip_prefix(['-', '+']).unwrap_or(e)))
+}
+
+/// A JSON string
+pub fn quote(text: &str) -> String {
+    let mut out = String::from('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn unnamed<'v, 'a>(items: &'v [ValueKind<'a>]) -> Vec<Entry<'v, 'a>> {
+    items
+        .iter()
+        .enumerate()
+        .map(|(i, v)| (String::new(), format!("[{i}]"), v))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::{parse_value_kind, ValueKind},
+        pretty::{self, lines, Notation},
+    };
+    use pretty_assertions::assert_eq;
+
+    fn text(value: &ValueKind, notation: Notation) -> Vec<String> {
+        lines(value, notation).into_iter().map(|l| l.text).collect()
+    }
+
+    const USER: &str = "User(id=1, name=a \"b\", tags=[x, Tag(v=1)], meta={}, other=null)";
+
+    #[test]
+    fn json() {
+        let (_, value) = parse_value_kind(USER).unwrap();
+        assert_eq!(
+            text(&value, Notation::Json),
+            vec![
+                "{",
+                "  \"id\": 1,",
+                "  \"name\": \"a \\\"b\\\"\",",
+                "  \"tags\": [",
+                "    \"x\",",
+                "    {",
+                "      \"v\": 1",
+                "    }",
+                "  ],",
+                "  \"meta\": {},",
+                "  \"other\": null",
+                "}",
+            ]
+        );
+    }
+
+    #[test]
+    fn json_numbers() {
+        let (_, value) = parse_value_kind("Zip(zip=01234, a=-0.5, b=1.5E3, c=0)").unwrap();
+        assert_eq!(
+            pretty::json(&value),
+            "{\"$class\":\"Zip\",\"zip\":\"01234\",\"a\":-0.5,\"b\":1.5E3,\"c\":0}"
+        );
+    }
+
+    #[test]
+    fn yaml() {
+        let (_, value) = parse_value_kind(USER).unwrap();
+        assert_eq!(
+            text(&value, Notation::Yaml),
+            vec![
+                "id: 1",
+                "name: \"a \\\"b\\\"\"",
+                "tags:",
+                "  - \"x\"",
+                "  - v: 1",
+                "meta: {}",
+                "other: null",
+            ]
+        );
+    }
+
+    #[test]
+    fn to_string() {
+        let (_, value) = parse_value_kind(USER).unwrap();
+        assert_eq!(
+            text(&value, Notation::ToString),
+            vec![
+                "User(",
+                "  id=1",
+                "  name=\"a \\\"b\\\"\"",
+                "  tags=[",
+                "    \"x\"",
+                "    Tag(",
+                "      v=1",
+                "    )",
+                "  ]",
+                "  meta={}",
+                "  other=null",
+                ")",
+            ]
+        );
+    }
+}
@@ -0,0 +1,232 @@
+use nom::branch::alt;
+use nom::bytes::complete::{tag, take_until, take_while1};
+use nom::character::complete::{char, multispace0};
+use nom::multi::many0;
+use nom::sequence::{delimited, preceded, separated_pair};
+use nom::IResult;
+
+/// A `<testcase>` of a Surefire or Gradle XML report that contains a `<failure>`
+#[derive(Debug, PartialEq)]
+pub struct TestCaseFailure {
+    pub class_name: String,
+    pub name: String,
+    /// The unescaped `message` attribute of the failure
+    pub message: String,
+    /// The unescaped text of the failure. Usually the exception with its stack trace
+    pub text: String,
+}
+
+/// Hint for a failed test case of a Surefire or Gradle XML report
+#[derive(Debug, PartialEq)]
+pub struct ReportHint {
+    pub class_name: String,
+    pub name: String,
+    pub hint: String,
+}
+
+/// Returns every failed test case of the report
+pub fn failures(xml: &str) -> Vec<TestCaseFailure> {
+    let mut out = vec![];
+    let mut input = xml;
+
+    while let Some(start) = input.find("<testcase") {
+        input = &input[start..];
+        match parse_test_case(input) {
+            Ok((rest, failure)) => {
+                out.extend(failure);
+                input = rest;
+            }
+            Err(_) => input = &input["<testcase".len()..],
+        }
+    }
+
+    out
+}
+
+type Attributes<'a> = Vec<(&'a str, &'a str)>;
+
+/// demo input: classname="com.example.UserTest"
+fn parse_attribute(input: &str) -> IResult<&str, (&str, &str)> {
+    preceded(
+        multispace0,
+        separated_pair(
+            take_while1(|c: char| c.is_alphanumeric() || c == '-' || c == '_' || c == ':'),
+            delimited(multispace0, char('='), multispace0),
+            alt((
+                delimited(char('"'), take_until("\""), char('"')),
+                delimited(char('\''), take_until("'"), char('\'')),
+            )),
+        ),
+    )(input)
+}
+
+/// demo input: <testcase name="list()" classname="com.example.UserTest">
+/// Returns the attributes and if the element is self closing
+fn parse_start_tag<'a>(
+    name: &'static str,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (Attributes<'a>, bool)> {
+    move |input| {
+        let (input, _) = tag("<")(input)?;
+        let (input, _) = tag(name)(input)?;
+        let (input, attributes) = many0(parse_attribute)(input)?;
+        let (input, _) = multispace0(input)?;
+        let (input, end) = alt((tag("/>"), tag(">")))(input)?;
+        Ok((input, (attributes, end == "/>")))
+    }
+}
+
+/// demo input: <testcase name="list()" classname="UserTest"><failure message="...">...</failure></testcase>
+fn parse_test_case(input: &str) -> IResult<&str, Option<TestCaseFailure>> {
+    let (input, (attributes, self_closing)) = parse_start_tag("testcase")(input)?;
+    if self_closing {
+        return Ok((input, None));
+    }
+    let (input, body) = take_until("</testcase>")(input)?;
+    let (input, _) = tag("</testcase>")(input)?;
+
+    let failure = parse_failure(body)
+        .ok()
+        .map(|(_, (message, text))| TestCaseFailure {
+            class_name: unescape(attribute(&attributes, "classname")),
+            name: unescape(attribute(&attributes, "name")),
+            message,
+            text,
+        });
+    Ok((input, failure))
+}
+
+/// demo input: <system-out/><failure message="expected: &lt;1&gt; but was: &lt;2&gt;">...</failure>
+fn parse_failure(input: &str) -> IResult<&str, (String, String)> {
+    let (input, _) = take_until("<failure")(input)?;
+    let (input, (attributes, self_closing)) = parse_start_tag("failure")(input)?;
+    let message = unescape(attribute(&attributes, "message"));
+    if self_closing {
+        return Ok((input, (message, String::new())));
+    }
+    let (input, text) = take_until("</failure>")(input)?;
+    Ok((input, (message, unescape(text))))
+}
+
+fn attribute<'a>(attributes: &Attributes<'a>, name: &str) -> &'a str {
+    attributes
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, v)| *v)
+        .unwrap_or_default()
+}
+
+/// Resolves xml entities and CDATA sections
+fn unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find(['&', '<']) {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+            let end = cdata.find("]]>").unwrap_or(cdata.len());
+            out.push_str(&cdata[..end]);
+            rest = cdata.get(end + 3..).unwrap_or_default();
+            continue;
+        }
+
+        let entity = rest
+            .find(';')
+            .and_then(|end| Some((resolve_entity(&rest[1..end])?, end)));
+        match entity {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push_str(&rest[..1]);
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+
+    out
+}
+
+/// demo input: lt, #10, #x0A
+fn resolve_entity(entity: &str) -> Option<char> {
+    match entity {
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "amp" => Some('&'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        _ => {
+            let number = entity.strip_prefix('#')?;
+            let code = match number.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => number.parse().ok()?,
+            };
+            char::from_u32(code)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::report::{failures, unescape, TestCaseFailure};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn unescape_test() {
+        assert_eq!(
+            unescape(
+                "expected: &lt;1&gt; but was: &lt;&#50;&gt;&#x0A;&amp;&unknown; <![CDATA[<a>]]>"
+            ),
+            "expected: <1> but was: <2>\n&&unknown; <a>"
+        );
+    }
+
+    #[test]
+    fn surefire_failures() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuite name="com.example.UserTest" tests="3" failures="1">
+  <properties>
+    <property name="java.version" value="21"/>
+  </properties>
+  <testcase name="equal" classname="com.example.UserTest" time="0.001"/>
+  <testcase name="basic" classname="com.example.UserTest" time="0.01">
+    <failure message="expected: &lt;1&gt; but was: &lt;2&gt;" type="org.opentest4j.AssertionFailedError"><![CDATA[org.opentest4j.AssertionFailedError: expected: <1> but was: <2>
+	at com.example.UserTest.basic(UserTest.java:12)
+]]></failure>
+  </testcase>
+  <testcase name="other" classname="com.example.UserTest" time="0.002">
+    <system-out>expected: nothing</system-out>
+  </testcase>
+</testsuite>"#;
+
+        assert_eq!(
+            failures(xml),
+            vec![TestCaseFailure {
+                class_name: "com.example.UserTest".to_string(),
+                name: "basic".to_string(),
+                message: "expected: <1> but was: <2>".to_string(),
+                text: "org.opentest4j.AssertionFailedError: expected: <1> but was: <2>\n\tat com.example.UserTest.basic(UserTest.java:12)\n".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn gradle_failures() {
+        let xml = r#"<testsuite name="UserTest"><testcase name="list()" classname="UserTest" time="0.003"><failure message="org.opentest4j.AssertionFailedError: expected: &lt;[a]&gt; but was: &lt;[b]&gt;" type="org.opentest4j.AssertionFailedError">org.opentest4j.AssertionFailedError: expected: &lt;[a]&gt; but was: &lt;[b]&gt;</failure></testcase></testsuite>"#;
+
+        assert_eq!(
+            failures(xml),
+            vec![TestCaseFailure {
+                class_name: "UserTest".to_string(),
+                name: "list()".to_string(),
+                message: "org.opentest4j.AssertionFailedError: expected: <[a]> but was: <[b]>"
+                    .to_string(),
+                text: "org.opentest4j.AssertionFailedError: expected: <[a]> but was: <[b]>"
+                    .to_string(),
+            }]
+        );
+    }
+}
@@ -0,0 +1,314 @@
+use std::collections::VecDeque;
+use std::io::{BufRead, Lines};
+
+use itertools::Itertools;
+
+use crate::{parser, DiffOptions, Format};
+
+/// Amount of lines that are joined when an assertion failure is spread over multiple lines
+const MAX_PENDING_LINES: usize = 32;
+
+/// Hint for an assertion failure found in a build log
+#[derive(Debug, PartialEq)]
+pub struct ScanHint {
+    /// 1 based number of the line where the assertion failure starts
+    pub line: usize,
+    pub hint: String,
+}
+
+/// Streams a build log line by line and yields a hint for every assertion failure.
+/// Only the lines of a failure that could not be parsed yet are kept in memory.
+pub struct Scanner<R> {
+    lines: Lines<R>,
+    line: usize,
+    color: bool,
+    options: DiffOptions,
+    format: Format,
+    /// Line number and text of the lines of an unfinished assertion failure
+    pending: VecDeque<(usize, String)>,
+    ready: VecDeque<ScanHint>,
+}
+
+impl<R: BufRead> Scanner<R> {
+    pub fn new(reader: R, color: bool, options: DiffOptions, format: Format) -> Self {
+        Self {
+            lines: reader.lines(),
+            line: 0,
+            color,
+            options,
+            format,
+            pending: VecDeque::new(),
+            ready: VecDeque::new(),
+        }
+    }
+
+    fn push(&mut self, line: String) {
+        if self.pending.is_empty() && !is_failure_start(&line) {
+            return;
+        }
+        self.pending.push_back((self.line, line));
+        self.scan_pending(false);
+    }
+
+    /// Tries to parse the pending lines. Starting with every line that could start a failure.
+    /// When `finished` is set no more lines will follow so unparsable lines are given up.
+    fn scan_pending(&mut self, finished: bool) {
+        loop {
+            let text = self.pending.iter().map(|(_, l)| l).join("\n");
+            let mut offset = 0;
+            let starts = self
+                .pending
+                .iter()
+                .enumerate()
+                .filter_map(|(i, (line, l))| {
+                    let start = is_failure_start(l).then_some((i, *line, offset));
+                    offset += l.len() + 1;
+                    start
+                })
+                .collect_vec();
+
+            let mut parsed = None;
+            for (i, line, offset) in starts {
+                let consumed = self.parse_all(&text[offset..], line);
+                if consumed > 0 {
+                    parsed = Some((i, consumed));
+                    break;
+                }
+            }
+
+            if let Some((start, consumed)) = parsed {
+                self.consume(start, consumed);
+                continue;
+            }
+
+            if finished || self.pending.len() > MAX_PENDING_LINES {
+                self.pending.pop_front();
+                self.skip_to_failure_start();
+                if !self.pending.is_empty() {
+                    continue;
+                }
+            }
+            break;
+        }
+    }
+
+    /// Parses every assertion failure from the start of the text.
+    /// Returns the amount of bytes that were consumed
+    fn parse_all(&mut self, text: &str, first_line: usize) -> usize {
+        let mut input = text;
+        while let Ok((rest, data)) = parser::parse(input) {
+            let start = text.len() - input.len() + failure_start(input, rest);
+            let line = first_line + text[..start].matches('\n').count();
+            let source = [("line", line.to_string())];
+            self.ready.push_back(ScanHint {
+                line,
+                hint: crate::hint_message(data, self.color, &self.options, self.format, &source),
+            });
+            input = rest;
+        }
+        text.len() - input.len()
+    }
+
+    /// Removes the lines before `start` and `consumed` bytes of the following lines
+    fn consume(&mut self, start: usize, mut consumed: usize) {
+        self.pending.drain(..start);
+        while let Some((_, line)) = self.pending.front_mut() {
+            if consumed <= line.len() {
+                line.drain(..consumed);
+                break;
+            }
+            consumed -= line.len() + 1;
+            self.pending.pop_front();
+        }
+        self.skip_to_failure_start();
+    }
+
+    fn skip_to_failure_start(&mut self) {
+        while self
+            .pending
+            .front()
+            .is_some_and(|(_, l)| !is_failure_start(l))
+        {
+            self.pending.pop_front();
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Scanner<R> {
+    type Item = std::io::Result<ScanHint>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(hint) = self.ready.pop_front() {
+                return Some(Ok(hint));
+            }
+            match self.lines.next() {
+                Some(Ok(line)) => {
+                    self.line += 1;
+                    self.push(line);
+                }
+                Some(Err(e)) => return Some(Err(e)),
+                None if self.pending.is_empty() => return None,
+                None => self.scan_pending(true),
+            }
+        }
+    }
+}
+
+fn is_failure_start(line: &str) -> bool {
+    line.contains("xpected") || line.contains("Expecting")
+}
+
+/// Where the failure that was parsed up to `rest` starts. The parsers skip the text in front of
+/// a failure, which can have words like "expected" too. So the failure starts at the last of these
+/// words from which the same failure is parsed
+fn failure_start(input: &str, rest: &str) -> usize {
+    let consumed = &input[..input.len() - rest.len()];
+    ["expected", "Expected", "Expecting"]
+        .into_iter()
+        .flat_map(|word| consumed.match_indices(word).map(|(i, _)| i))
+        .sorted_unstable()
+        .rev()
+        .find(|&i| parser::parse(&input[i..]).is_ok_and(|(r, _)| r.len() == rest.len()))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        scan::{ScanHint, Scanner},
+        DiffOptions, Format,
+    };
+    use pretty_assertions::assert_eq;
+
+    fn scan(log: &str) -> Vec<ScanHint> {
+        scan_with_format(log, Format::Line)
+    }
+
+    fn scan_with_format(log: &str, format: Format) -> Vec<ScanHint> {
+        Scanner::new(log.as_bytes(), false, DiffOptions::default(), format)
+            .map(|h| h.unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn every_failure() {
+        let log = "[INFO] Running com.example.UserTest
+[ERROR] Tests run: 3, Failures: 2, Errors: 0, Skipped: 0
+[ERROR] com.example.UserTest.basic  Time elapsed: 0.01 s  <<< FAILURE!
+org.opentest4j.AssertionFailedError: expected: <User(firstName=null, lastname=asd)> but was: <User(firstName=null, lastname=aaa)>
+	at com.example.UserTest.basic(UserTest.java:12)
+[ERROR] com.example.UserTest.string  Time elapsed: 0.01 s  <<< FAILURE!
+org.opentest4j.AssertionFailedError: expected: <1> but was: <2>
+";
+
+        assert_eq!(
+            scan(log),
+            vec![
+                ScanHint {
+                    line: 4,
+                    hint: " -> User(.lastname -> [-asd-]{+aaa+})".to_string()
+                },
+                ScanHint {
+                    line: 7,
+                    hint: " -> 2 (off by 1)".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn same_line() {
+        assert_eq!(
+            scan("expected: <1> but was: <2> and expected: <a> but was: <b>"),
+            vec![
+                ScanHint {
+                    line: 1,
+                    hint: " -> 2 (off by 1)".to_string()
+                },
+                ScanHint {
+                    line: 1,
+                    hint: " -> [-a-]{+b+}".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn wrapped_lines() {
+        let log = "UserTest > basic() FAILED
+    org.opentest4j.AssertionFailedError: expected: <User(firstName=null,
+        lastname=asd)> but was: <User(firstName=null,
+        lastname=aaa)>
+        at UserTest.basic(UserTest.java:12)";
+
+        assert_eq!(
+            scan(log),
+            vec![ScanHint {
+                line: 2,
+                hint: " -> User(.lastname -> [-asd-]{+aaa+})".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn assertj() {
+        let log = "[ERROR] com.example.UserTest.string  Time elapsed: 0.01 s  <<< FAILURE!
+org.opentest4j.AssertionFailedError:
+
+expected: \"Hello\"
+ but was: \"World\"
+	at com.example.UserTest.string(UserTest.java:12)";
+
+        assert_eq!(
+            scan(log),
+            vec![ScanHint {
+                line: 4,
+                hint: " -> [-Hello-]{+World+}".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn false_start() {
+        let log = "expected: nothing
+org.opentest4j.AssertionFailedError: expected: <1> but was: <2>
+expected: <unfinished";
+
+        assert_eq!(
+            scan(log),
+            vec![ScanHint {
+                line: 2,
+                hint: " -> 2 (off by 1)".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn skipped_start() {
+        let log = "[INFO] all tests are expected to pass
+org.opentest4j.AssertionFailedError: expected: <1> but was: <2>";
+
+        assert_eq!(
+            scan(log),
+            vec![ScanHint {
+                line: 2,
+                hint: " -> 2 (off by 1)".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn json_line() {
+        let log = "[ERROR] com.example.UserTest.string  Time elapsed: 0.01 s  <<< FAILURE!
+org.opentest4j.AssertionFailedError: expected: <a> but was: <b>";
+
+        assert_eq!(
+            scan_with_format(log, Format::JsonLines),
+            vec![ScanHint {
+                line: 2,
+                hint: "{\"schema_version\":1,\"line\":2,\"path\":\"$\",\"kind\":\"changed\",\"expected\":\"a\",\"actual\":\"b\"}\n{\"schema_version\":1,\"line\":2,\"equal\":false,\"ignored\":0,\"warnings\":[],\"changes\":1}".to_string()
+            }]
+        );
+    }
+}
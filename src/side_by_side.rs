@@ -0,0 +1,140 @@
+use colored::Colorize;
+use itertools::Itertools;
+
+use crate::{
+    change::Change,
+    parser::ValueKind,
+    pretty::{self, Notation, Row},
+};
+
+/// Writes the expected and the real value next to each other with one field per row.
+/// Rows are marked like sdiff: ` ` same, `|` changed, `<` only expected and `>` only real.
+/// Rows that are longer than a column continue on the next line
+pub fn side_by_side(
+    expected: &ValueKind,
+    real: &ValueKind,
+    changes: &[Change],
+    color: bool,
+    width: usize,
+) -> String {
+    let left = pretty::lines(expected, Notation::ToString);
+    let right = pretty::lines(real, Notation::ToString);
+
+    let column = (width.saturating_sub(3) / 2).max(1);
+    let mut out = format!("\n{:column$} | actual", "expected");
+    for row in pretty::rows(&left, &right, changes) {
+        let (marker, l, r) = match row {
+            Row::Same(l, r) => (' ', l.text.as_str(), r.text.as_str()),
+            Row::Changed(Some(l), Some(r)) => ('|', l.text.as_str(), r.text.as_str()),
+            Row::Changed(Some(l), None) => ('<', l.text.as_str(), ""),
+            Row::Changed(None, r) => ('>', "", r.map_or("", |r| r.text.as_str())),
+        };
+        for pair in wrap(l, column).into_iter().zip_longest(wrap(r, column)) {
+            let (l, r) = pair.or_default();
+            let l = format!("{l:column$}");
+            out.push_str(&match (color, marker) {
+                (true, ' ') => format!("\n{}", format!("{l}   {r}").trim_end().dimmed()),
+                (true, _) => format!("\n{} {marker} {}", l.red(), r.green()),
+                (false, _) => format!("\n{}", format!("{l} {marker} {r}").trim_end()),
+            });
+        }
+    }
+    out
+}
+
+/// Splits the text into pieces of the width of a column
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let chars = text.chars().collect_vec();
+    match chars.is_empty() {
+        true => vec![String::new()],
+        false => chars.chunks(width).map(|c| c.iter().collect()).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{get_hint_with_format, DiffOptions, Format};
+    use pretty_assertions::assert_eq;
+
+    fn side_by_side(text: &str, width: usize) -> Option<String> {
+        let format = Format::SideBySide { width };
+        get_hint_with_format(text, false, &DiffOptions::default(), format)
+    }
+
+    #[test]
+    fn side_by_side_rows() {
+        assert_eq!(
+            side_by_side(
+                "org.opentest4j.AssertionFailedError: expected: <User(id=1, name=a, tags=[x], meta={})> but was: <User(id=1, name=b, tags=[x, y], meta={})>",
+                43
+            ),
+            Some(
+                [
+                    "",
+                    "expected             | actual",
+                    "User(                  User(",
+                    "  id=1                   id=1",
+                    "  name=\"a\"           |   name=\"b\"",
+                    "  tags=[                 tags=[",
+                    "    \"x\"                    \"x\"",
+                    "                     >     \"y\"",
+                    "  ]                      ]",
+                    "  meta={}                meta={}",
+                    ")                      )",
+                ]
+                .join("\n")
+            )
+        );
+    }
+
+    #[test]
+    fn side_by_side_width() {
+        assert_eq!(
+            side_by_side(
+                "org.opentest4j.AssertionFailedError: expected: <User(description=a long text)> but was: <User(description=another long text)>",
+                23
+            ),
+            Some(
+                [
+                    "",
+                    "expected   | actual",
+                    "User(        User(",
+                    "  descript |   descript",
+                    "ion=\"a lon | ion=\"anoth",
+                    "g text\"    | er long te",
+                    "           | xt\"",
+                    ")            )",
+                ]
+                .join("\n")
+            )
+        );
+    }
+
+    #[test]
+    fn side_by_side_ignore() {
+        let options = DiffOptions {
+            ignore: vec!["ts".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            get_hint_with_format(
+                "org.opentest4j.AssertionFailedError: expected: <User(ts=5, name=a)> but was: <User(ts=6, name=b)>",
+                false,
+                &options,
+                Format::SideBySide { width: 43 }
+            ),
+            Some(
+                [
+                    "",
+                    "expected             | actual",
+                    "User(                  User(",
+                    "  ts=5                   ts=6",
+                    "  name=\"a\"           |   name=\"b\"",
+                    ")                      )",
+                    "(1 ignored)",
+                ]
+                .join("\n")
+            )
+        );
+    }
+}
@@ -0,0 +1,148 @@
+use itertools::Itertools;
+
+use crate::{diff::Difference, message};
+
+/// Writes the difference like [message::message] with the amount of changes of every Dto and list.
+/// Dtos and lists deeper than `depth` and the changes after the first `changes` of one are left out
+pub fn summary(diff: Difference, color: bool, depth: usize, changes: usize) -> String {
+    let limits = Limits { depth, changes };
+    node(diff, color, 0, limits)
+}
+
+#[derive(Clone, Copy)]
+struct Limits {
+    depth: usize,
+    changes: usize,
+}
+
+fn node(diff: Difference, color: bool, depth: usize, limits: Limits) -> String {
+    match diff {
+        Difference::DtoChange((name, o)) => {
+            let counts = counts(&o, "field");
+            let children = children(o, color, depth, limits);
+            format!(" -> {name}({children}) ({counts})")
+        }
+        Difference::ArrayChange(o) => {
+            let counts = counts(&o, "entry");
+            let children = children(o, color, depth, limits);
+            format!(" -> [{children}] ({counts})")
+        }
+        Difference::FieldValueChange((name, diff)) => {
+            format!(".{name}{}", node(*diff, color, depth, limits))
+        }
+        Difference::ParentChange(diff) => format!(".super{}", node(*diff, color, depth, limits)),
+        Difference::Changed((i, _, id, diff)) => format!(
+            " changed at [{i}]{}{}",
+            id.map(|id| format!(" {id}")).unwrap_or_default(),
+            node(*diff, color, depth, limits)
+        ),
+        diff => message::message(diff, color),
+    }
+}
+
+/// The changes of a Dto or list up to the limits
+fn children(o: Vec<Difference>, color: bool, depth: usize, limits: Limits) -> String {
+    let shown = match depth < limits.depth {
+        true => limits.changes,
+        false => 0,
+    };
+    let changes = o
+        .into_iter()
+        .filter(|d| *d != Difference::Equal)
+        .collect_vec();
+    let more = changes.len().saturating_sub(shown);
+    let mut out = changes
+        .into_iter()
+        .take(shown)
+        .map(|d| node(d, color, depth + 1, limits))
+        .join("");
+    if more > 0 {
+        let space = if out.is_empty() { "" } else { " " };
+        out.push_str(&format!("{space}... {more} more"));
+    }
+    out
+}
+
+/// How many of the children were changed in which way: 3 fields changed, 1 element added, 12 unchanged.
+/// `field` names the named children, which are fields of Dtos and entries of maps
+fn counts(o: &[Difference], field: &'static str) -> String {
+    let mut counts: Vec<(&str, &str, usize)> = vec![];
+    let mut unchanged = 0;
+    for d in o {
+        let (noun, verb) = match d {
+            Difference::Equal => {
+                unchanged += 1;
+                continue;
+            }
+            Difference::FieldValueChange(_) | Difference::FieldNameChange(_) => (field, "changed"),
+            Difference::MissingField(_) => ("field", "missing"),
+            Difference::ExtraField(_) => ("field", "added"),
+            Difference::ParentChange(_) => ("parent", "changed"),
+            Difference::UndefinedLeft(_) => ("entry", "added"),
+            Difference::UndefinedRight(_) => ("entry", "removed"),
+            Difference::Inserted(_) => ("element", "added"),
+            Difference::Removed(_) => ("element", "removed"),
+            Difference::Moved(_) => ("element", "moved"),
+            _ => ("element", "changed"),
+        };
+        match counts.iter_mut().find(|c| c.0 == noun && c.1 == verb) {
+            Some(count) => count.2 += 1,
+            None => counts.push((noun, verb, 1)),
+        }
+    }
+    let mut out = counts
+        .into_iter()
+        .map(|(noun, verb, n)| format!("{n} {} {verb}", plural(noun, n)))
+        .collect_vec();
+    if unchanged > 0 {
+        out.push(format!("{unchanged} unchanged"));
+    }
+    out.join(", ")
+}
+
+fn plural(noun: &str, n: usize) -> String {
+    match (n, noun) {
+        (1, noun) => noun.to_string(),
+        (_, "entry") => "entries".to_string(),
+        (_, noun) => format!("{noun}s"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{get_hint_with_format, DiffOptions, Format};
+    use pretty_assertions::assert_eq;
+
+    fn summary(text: &str, depth: usize, changes: usize) -> Option<String> {
+        let format = Format::Summary { depth, changes };
+        get_hint_with_format(text, false, &DiffOptions::default(), format)
+    }
+
+    #[test]
+    fn summary_counts() {
+        assert_eq!(
+            summary(
+                "org.opentest4j.AssertionFailedError: expected: <User(id=1, name=a, age=2, city=x, tags=[a])> but was: <User(id=1, name=b, age=3, city=x, tags=[a, b], zip=1)>",
+                3,
+                10
+            ),
+            Some(" -> User(.name -> [-a-]{+b+}.age -> 3 (off by 1).tags -> [ inserted at [1] \"b\"] (1 element added) extra field zip=1) (3 fields changed, 1 field added, 2 unchanged)".to_string())
+        );
+    }
+
+    #[test]
+    fn summary_limits() {
+        let text = "org.opentest4j.AssertionFailedError: expected: <Order(id=1, user=User(a=1, b=2, c=3), items=[1, 2, 3])> but was: <Order(id=2, user=User(a=2, b=3, c=4), items=[1, 2, 3])>";
+        assert_eq!(
+            summary(text, 1, 10),
+            Some(" -> Order(.id -> 2 (off by 1).user -> User(... 3 more) (3 fields changed)) (2 fields changed, 1 unchanged)".to_string())
+        );
+        assert_eq!(
+            summary(text, 3, 1),
+            Some(
+                " -> Order(.id -> 2 (off by 1) ... 1 more) (2 fields changed, 1 unchanged)"
+                    .to_string()
+            )
+        );
+    }
+}
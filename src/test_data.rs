@@ -6,265 +6,274 @@ pub fn get_complicated_expected() -> AssertionFailedError<'static> {
             name: "Complicated",
             fields: vec![
                 DtoField {
-                    name: "a",
-                    value: ValueKind::String("hey"),
+                    name: "a".into(),
+                    value: ValueKind::String("hey".into()),
                 },
                 DtoField {
-                    name: "b",
-                    value: ValueKind::String("2"),
+                    name: "b".into(),
+                    value: ValueKind::Integer("2".into()),
                 },
                 DtoField {
-                    name: "c",
-                    value: ValueKind::String("500"),
+                    name: "c".into(),
+                    value: ValueKind::Integer("500".into()),
                 },
                 DtoField {
-                    name: "d",
-                    value: ValueKind::String("600"),
+                    name: "d".into(),
+                    value: ValueKind::Integer("600".into()),
                 },
                 DtoField {
-                    name: "e",
+                    name: "e".into(),
                     value: ValueKind::Map(vec![ValueKind::Field(Box::new(DtoField {
-                        name: "eee",
+                        name: "eee".into(),
                         value: ValueKind::Dto(Dto {
                             name: "Complicated",
                             fields: vec![
                                 DtoField {
-                                    name: "a",
-                                    value: ValueKind::String("a"),
+                                    name: "a".into(),
+                                    value: ValueKind::String("a".into()),
                                 },
                                 DtoField {
-                                    name: "b",
-                                    value: ValueKind::String("2"),
+                                    name: "b".into(),
+                                    value: ValueKind::Integer("2".into()),
                                 },
                                 DtoField {
-                                    name: "c",
-                                    value: ValueKind::String("500"),
+                                    name: "c".into(),
+                                    value: ValueKind::Integer("500".into()),
                                 },
                                 DtoField {
-                                    name: "d",
-                                    value: ValueKind::String("600"),
+                                    name: "d".into(),
+                                    value: ValueKind::Integer("600".into()),
                                 },
                                 DtoField {
-                                    name: "e",
-                                    value: ValueKind::Map(vec![ValueKind::String("")]),
+                                    name: "e".into(),
+                                    value: ValueKind::Map(vec![]),
                                 },
                                 DtoField {
-                                    name: "f",
-                                    value: ValueKind::Array(vec![ValueKind::String("")]),
+                                    name: "f".into(),
+                                    value: ValueKind::Array(vec![]),
                                 },
                                 DtoField {
-                                    name: "g",
-                                    value: ValueKind::Array(vec![ValueKind::String("")]),
+                                    name: "g".into(),
+                                    value: ValueKind::Array(vec![]),
                                 },
                             ],
+                            ..Default::default()
                         }),
                     }))]),
                 },
                 DtoField {
-                    name: "f",
+                    name: "f".into(),
                     value: ValueKind::Array(vec![ValueKind::Dto(Dto {
                         name: "Complicated",
                         fields: vec![
                             DtoField {
-                                name: "a",
-                                value: ValueKind::String("thing"),
+                                name: "a".into(),
+                                value: ValueKind::String("thing".into()),
                             },
                             DtoField {
-                                name: "b",
-                                value: ValueKind::String("2"),
+                                name: "b".into(),
+                                value: ValueKind::Integer("2".into()),
                             },
                             DtoField {
-                                name: "c",
-                                value: ValueKind::String("500"),
+                                name: "c".into(),
+                                value: ValueKind::Integer("500".into()),
                             },
                             DtoField {
-                                name: "d",
-                                value: ValueKind::String("600"),
+                                name: "d".into(),
+                                value: ValueKind::Integer("600".into()),
                             },
                             DtoField {
-                                name: "e",
-                                value: ValueKind::Map(vec![ValueKind::String("")]),
+                                name: "e".into(),
+                                value: ValueKind::Map(vec![]),
                             },
                             DtoField {
-                                name: "f",
-                                value: ValueKind::Array(vec![ValueKind::String("")]),
+                                name: "f".into(),
+                                value: ValueKind::Array(vec![]),
                             },
                             DtoField {
-                                name: "g",
-                                value: ValueKind::Array(vec![ValueKind::String("")]),
+                                name: "g".into(),
+                                value: ValueKind::Array(vec![]),
                             },
                         ],
+                        ..Default::default()
                     })]),
                 },
                 DtoField {
-                    name: "g",
+                    name: "g".into(),
                     value: ValueKind::Array(vec![ValueKind::Dto(Dto {
                         name: "Complicated",
                         fields: vec![
                             DtoField {
-                                name: "a",
-                                value: ValueKind::String("hehe"),
+                                name: "a".into(),
+                                value: ValueKind::String("hehe".into()),
                             },
                             DtoField {
-                                name: "b",
-                                value: ValueKind::String("2"),
+                                name: "b".into(),
+                                value: ValueKind::Integer("2".into()),
                             },
                             DtoField {
-                                name: "c",
-                                value: ValueKind::String("500"),
+                                name: "c".into(),
+                                value: ValueKind::Integer("500".into()),
                             },
                             DtoField {
-                                name: "d",
-                                value: ValueKind::String("600"),
+                                name: "d".into(),
+                                value: ValueKind::Integer("600".into()),
                             },
                             DtoField {
-                                name: "e",
-                                value: ValueKind::Map(vec![ValueKind::String("")]),
+                                name: "e".into(),
+                                value: ValueKind::Map(vec![]),
                             },
                             DtoField {
-                                name: "f",
-                                value: ValueKind::Array(vec![ValueKind::String("")]),
+                                name: "f".into(),
+                                value: ValueKind::Array(vec![]),
                             },
                             DtoField {
-                                name: "g",
-                                value: ValueKind::Array(vec![ValueKind::String("")]),
+                                name: "g".into(),
+                                value: ValueKind::Array(vec![]),
                             },
                         ],
+                        ..Default::default()
                     })]),
                 },
             ],
+            ..Default::default()
         }),
         real: ValueKind::Dto(Dto {
             name: "Complicated",
             fields: vec![
                 DtoField {
-                    name: "a",
-                    value: ValueKind::String("hey"),
+                    name: "a".into(),
+                    value: ValueKind::String("hey".into()),
                 },
                 DtoField {
-                    name: "b",
-                    value: ValueKind::String("2"),
+                    name: "b".into(),
+                    value: ValueKind::Integer("2".into()),
                 },
                 DtoField {
-                    name: "c",
-                    value: ValueKind::String("500"),
+                    name: "c".into(),
+                    value: ValueKind::Integer("500".into()),
                 },
                 DtoField {
-                    name: "d",
-                    value: ValueKind::String("600"),
+                    name: "d".into(),
+                    value: ValueKind::Integer("600".into()),
                 },
                 DtoField {
-                    name: "e",
+                    name: "e".into(),
                     value: ValueKind::Map(vec![ValueKind::Field(Box::new(DtoField {
-                        name: "eee",
+                        name: "eee".into(),
                         value: ValueKind::Dto(Dto {
                             name: "Complicated",
                             fields: vec![
                                 DtoField {
-                                    name: "a",
-                                    value: ValueKind::String("b"),
+                                    name: "a".into(),
+                                    value: ValueKind::String("b".into()),
                                 },
                                 DtoField {
-                                    name: "b",
-                                    value: ValueKind::String("2"),
+                                    name: "b".into(),
+                                    value: ValueKind::Integer("2".into()),
                                 },
                                 DtoField {
-                                    name: "c",
-                                    value: ValueKind::String("500"),
+                                    name: "c".into(),
+                                    value: ValueKind::Integer("500".into()),
                                 },
                                 DtoField {
-                                    name: "d",
-                                    value: ValueKind::String("600"),
+                                    name: "d".into(),
+                                    value: ValueKind::Integer("600".into()),
                                 },
                                 DtoField {
-                                    name: "e",
-                                    value: ValueKind::Map(vec![ValueKind::String("")]),
+                                    name: "e".into(),
+                                    value: ValueKind::Map(vec![]),
                                 },
                                 DtoField {
-                                    name: "f",
-                                    value: ValueKind::Array(vec![ValueKind::String("")]),
+                                    name: "f".into(),
+                                    value: ValueKind::Array(vec![]),
                                 },
                                 DtoField {
-                                    name: "g",
-                                    value: ValueKind::Array(vec![ValueKind::String("")]),
+                                    name: "g".into(),
+                                    value: ValueKind::Array(vec![]),
                                 },
                             ],
+                            ..Default::default()
                         }),
                     }))]),
                 },
                 DtoField {
-                    name: "f",
+                    name: "f".into(),
                     value: ValueKind::Array(vec![ValueKind::Dto(Dto {
                         name: "Complicated",
                         fields: vec![
                             DtoField {
-                                name: "a",
-                                value: ValueKind::String("thing"),
+                                name: "a".into(),
+                                value: ValueKind::String("thing".into()),
                             },
                             DtoField {
-                                name: "b",
-                                value: ValueKind::String("2"),
+                                name: "b".into(),
+                                value: ValueKind::Integer("2".into()),
                             },
                             DtoField {
-                                name: "c",
-                                value: ValueKind::String("500"),
+                                name: "c".into(),
+                                value: ValueKind::Integer("500".into()),
                             },
                             DtoField {
-                                name: "d",
-                                value: ValueKind::String("600"),
+                                name: "d".into(),
+                                value: ValueKind::Integer("600".into()),
                             },
                             DtoField {
-                                name: "e",
-                                value: ValueKind::Map(vec![ValueKind::String("")]),
+                                name: "e".into(),
+                                value: ValueKind::Map(vec![]),
                             },
                             DtoField {
-                                name: "f",
-                                value: ValueKind::Array(vec![ValueKind::String("")]),
+                                name: "f".into(),
+                                value: ValueKind::Array(vec![]),
                             },
                             DtoField {
-                                name: "g",
-                                value: ValueKind::Array(vec![ValueKind::String("")]),
+                                name: "g".into(),
+                                value: ValueKind::Array(vec![]),
                             },
                         ],
+                        ..Default::default()
                     })]),
                 },
                 DtoField {
-                    name: "g",
+                    name: "g".into(),
                     value: ValueKind::Array(vec![ValueKind::Dto(Dto {
                         name: "Complicated",
                         fields: vec![
                             DtoField {
-                                name: "a",
-                                value: ValueKind::String("hehe"),
+                                name: "a".into(),
+                                value: ValueKind::String("hehe".into()),
                             },
                             DtoField {
-                                name: "b",
-                                value: ValueKind::String("2"),
+                                name: "b".into(),
+                                value: ValueKind::Integer("2".into()),
                             },
                             DtoField {
-                                name: "c",
-                                value: ValueKind::String("500"),
+                                name: "c".into(),
+                                value: ValueKind::Integer("500".into()),
                             },
                             DtoField {
-                                name: "d",
-                                value: ValueKind::String("600"),
+                                name: "d".into(),
+                                value: ValueKind::Integer("600".into()),
                             },
                             DtoField {
-                                name: "e",
-                                value: ValueKind::Map(vec![ValueKind::String("")]),
+                                name: "e".into(),
+                                value: ValueKind::Map(vec![]),
                             },
                             DtoField {
-                                name: "f",
-                                value: ValueKind::Array(vec![ValueKind::String("")]),
+                                name: "f".into(),
+                                value: ValueKind::Array(vec![]),
                             },
                             DtoField {
-                                name: "g",
-                                value: ValueKind::Array(vec![ValueKind::String("")]),
+                                name: "g".into(),
+                                value: ValueKind::Array(vec![]),
                             },
                         ],
+                        ..Default::default()
                     })]),
                 },
             ],
+            ..Default::default()
         }),
+        warnings: vec![],
     }
 }
@@ -0,0 +1,306 @@
+use colored::Colorize;
+
+use crate::{
+    change::{entry, field_step, field_value, item, parent},
+    diff::Difference,
+    message::{message, message_value},
+    parser::ValueKind,
+};
+
+/// Writes one change per line with its path, indented by how deep it is.
+/// Unchanged fields and elements are left out except for `context` of them next to a change
+pub fn tree(
+    diff: Difference,
+    expected: &ValueKind,
+    real: &ValueKind,
+    color: bool,
+    context: usize,
+) -> String {
+    let tree = Tree { color, context };
+    let mut lines = vec![];
+    tree.node(diff, "$", Some(expected), Some(real), 0, &mut lines);
+    lines.into_iter().map(|line| format!("\n{line}")).collect()
+}
+
+struct Tree {
+    color: bool,
+    context: usize,
+}
+
+enum Row {
+    Changed(Vec<String>),
+    Unchanged(String),
+}
+
+impl Tree {
+    /// Values that only one side has are written with the path of their Dto, map or list
+    fn node(
+        &self,
+        diff: Difference,
+        path: &str,
+        expected: Option<&ValueKind>,
+        real: Option<&ValueKind>,
+        depth: usize,
+        lines: &mut Vec<String>,
+    ) {
+        let indent = "  ".repeat(depth);
+        match diff {
+            Difference::Equal => {}
+            Difference::DtoChange((name, o)) => {
+                lines.push(format!("{indent}{path} -> {name}"));
+                let rows = self.rows(o, path, expected, real, depth + 1);
+                self.elide(rows, depth + 1, lines);
+            }
+            Difference::ArrayChange(o) => {
+                let brackets = match expected {
+                    Some(ValueKind::Map(_)) => "{..}",
+                    Some(ValueKind::Tuple(_)) => "(..)",
+                    _ => "[..]",
+                };
+                lines.push(format!("{indent}{path} -> {brackets}"));
+                let rows = self.rows(o, path, expected, real, depth + 1);
+                self.elide(rows, depth + 1, lines);
+            }
+            Difference::FieldValueChange((name, diff)) => {
+                let path = format!("{path}{}", field_step(expected, &name));
+                let expected = expected.and_then(|e| field_value(e, &name));
+                let real = real.and_then(|r| field_value(r, &name));
+                self.node(*diff, &path, expected, real, depth, lines);
+            }
+            Difference::ParentChange(diff) => {
+                let expected = expected.and_then(parent);
+                let real = real.and_then(parent);
+                self.node(
+                    *diff,
+                    &format!("{path}.super"),
+                    expected,
+                    real,
+                    depth,
+                    lines,
+                );
+            }
+            Difference::Changed((i, j, _, diff)) => {
+                let expected = expected.and_then(|e| item(e, i));
+                let real = real.and_then(|r| item(r, j));
+                self.node(*diff, &format!("{path}[{i}]"), expected, real, depth, lines);
+            }
+            Difference::MissingField(field) => lines.push(format!(
+                "{indent}{path}{} missing {}",
+                field_step(expected, &field.name),
+                message_value(Some(field.value), self.color)
+            )),
+            Difference::ExtraField(field) => lines.push(format!(
+                "{indent}{path}{} extra {}",
+                field_step(real, &field.name),
+                message_value(Some(field.value), self.color)
+            )),
+            Difference::UndefinedRight(value) => {
+                let (path, value) = entry(path, expected, value.as_ref());
+                let value = message_value(value.cloned(), self.color);
+                lines.push(format!("{indent}{path} missing {value}"));
+            }
+            Difference::UndefinedLeft(value) => {
+                let (path, value) = entry(path, real, value.as_ref());
+                let value = message_value(value.cloned(), self.color);
+                lines.push(format!("{indent}{path} additional {value}"));
+            }
+            Difference::Removed((i, value)) => lines.push(format!(
+                "{indent}{path}[{i}] removed {}",
+                message_value(Some(value), self.color)
+            )),
+            Difference::Inserted((j, value)) => lines.push(format!(
+                "{indent}{path}[{j}] inserted {}",
+                message_value(Some(value), self.color)
+            )),
+            Difference::Moved((i, j)) => lines.push(format!("{indent}{path}[{i}] moved to [{j}]")),
+            diff => lines.push(format!("{indent}{path}{}", message(diff, self.color))),
+        }
+    }
+
+    /// The fields, entries or elements of the expected value in order with their changes.
+    /// What the expected value does not have comes last
+    fn rows(
+        &self,
+        mut o: Vec<Difference>,
+        path: &str,
+        expected: Option<&ValueKind>,
+        real: Option<&ValueKind>,
+        depth: usize,
+    ) -> Vec<Row> {
+        o.retain(|d| *d != Difference::Equal);
+        let mut rows = vec![];
+        let changed = |d: Difference, rows: &mut Vec<Row>| {
+            let mut lines = vec![];
+            self.node(d, path, expected, real, depth, &mut lines);
+            rows.push(Row::Changed(lines));
+        };
+        if let Some(i) = o
+            .iter()
+            .position(|d| matches!(d, Difference::ParentChange(_)))
+        {
+            changed(o.remove(i), &mut rows);
+        }
+
+        let children: Vec<(String, &ValueKind)> = match expected {
+            Some(ValueKind::Dto(dto)) => dto
+                .fields
+                .iter()
+                .map(|f| (f.name.to_string(), &f.value))
+                .collect(),
+            Some(ValueKind::Map(entries)) => entries
+                .iter()
+                .filter_map(|e| match e {
+                    ValueKind::Field(f) => Some((f.name.to_string(), &f.value)),
+                    _ => None,
+                })
+                .collect(),
+            Some(ValueKind::Array(items) | ValueKind::Tuple(items)) => items
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (i.to_string(), v))
+                .collect(),
+            _ => vec![],
+        };
+        let is_list = matches!(expected, Some(ValueKind::Array(_) | ValueKind::Tuple(_)));
+        for (key, value) in children {
+            let change = o.iter().position(|d| match is_list {
+                true => index(d).is_some_and(|i| i.to_string() == key),
+                false => name(d) == Some(&key),
+            });
+            match change {
+                Some(c) => changed(o.remove(c), &mut rows),
+                None => {
+                    let step = match is_list {
+                        true => format!("[{key}]"),
+                        false => field_step(expected, &key),
+                    };
+                    let value = message_value(Some(value.clone()), false);
+                    rows.push(Row::Unchanged(format!("{path}{step} = {value}")));
+                }
+            }
+        }
+        for d in o {
+            changed(d, &mut rows);
+        }
+        rows
+    }
+
+    /// Writes the changed rows and the unchanged ones next to them. The rest is counted
+    fn elide(&self, rows: Vec<Row>, depth: usize, lines: &mut Vec<String>) {
+        let indent = "  ".repeat(depth);
+        let near_change = |k: usize| {
+            let start = k.saturating_sub(self.context);
+            let end = (k + self.context).min(rows.len() - 1);
+            self.context > 0
+                && rows[start..=end]
+                    .iter()
+                    .any(|r| matches!(r, Row::Changed(_)))
+        };
+        let keep = (0..rows.len()).map(near_change).collect::<Vec<_>>();
+        let mut elided = 0;
+        for (k, row) in rows.iter().enumerate() {
+            match row {
+                Row::Unchanged(_) if !keep[k] => {
+                    elided += 1;
+                    continue;
+                }
+                _ if elided > 0 => {
+                    lines.push(format!("{indent}\u{2026} {elided} unchanged"));
+                    elided = 0;
+                }
+                _ => {}
+            }
+            match row {
+                Row::Changed(changed) => lines.extend(changed.iter().cloned()),
+                Row::Unchanged(line) if self.color => {
+                    lines.push(format!("{indent}{}", line.dimmed()))
+                }
+                Row::Unchanged(line) => lines.push(format!("{indent}{line}")),
+            }
+        }
+        if elided > 0 {
+            lines.push(format!("{indent}\u{2026} {elided} unchanged"));
+        }
+    }
+}
+
+/// Field or map key that a change belongs to
+fn name<'d>(diff: &'d Difference) -> Option<&'d str> {
+    match diff {
+        Difference::FieldValueChange((name, _)) | Difference::FieldNameChange((name, _)) => {
+            Some(name)
+        }
+        Difference::MissingField(field) => Some(&field.name),
+        Difference::UndefinedRight(Some(ValueKind::Field(field))) => Some(&field.name),
+        _ => None,
+    }
+}
+
+/// Index of the expected list that a change belongs to
+fn index(diff: &Difference) -> Option<usize> {
+    match diff {
+        Difference::Changed((i, _, _, _))
+        | Difference::Removed((i, _))
+        | Difference::Moved((i, _)) => Some(*i),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{get_hint_with_format, DiffOptions, Format};
+    use pretty_assertions::assert_eq;
+
+    fn tree(text: &str, context: usize) -> Option<String> {
+        let format = Format::Tree { context };
+        get_hint_with_format(text, false, &DiffOptions::default(), format)
+    }
+
+    #[test]
+    fn tree_lines() {
+        assert_eq!(
+            tree(
+                "org.opentest4j.AssertionFailedError: expected: <Complicated(a=hey, b=2, c=500, d=600, e={eee=Complicated(a=a, b=2, c=500, d=600, e={}, f=[], g=[])}, f=[], g=[])> but was: <Complicated(a=hey, b=2, c=500, d=600, e={eee=Complicated(a=b, b=2, c=500, d=600, e={}, f=[], g=[])}, f=[], g=[])>",
+                0
+            ),
+            Some(
+                [
+                    "",
+                    "$ -> Complicated",
+                    "  \u{2026} 4 unchanged",
+                    "  $.e -> {..}",
+                    "    $.e[\"eee\"] -> Complicated",
+                    "      $.e[\"eee\"].a -> [-a-]{+b+}",
+                    "      \u{2026} 6 unchanged",
+                    "  \u{2026} 2 unchanged",
+                ]
+                .join("\n")
+            )
+        );
+    }
+
+    #[test]
+    fn tree_context() {
+        assert_eq!(
+            tree(
+                "org.opentest4j.AssertionFailedError: expected: <User(id=1, name=a, age=2, city=x, zip=1, tags=[a, b])> but was: <User(id=1, name=a, age=3, city=x, zip=1, tags=[b])>",
+                1
+            ),
+            Some(
+                [
+                    "",
+                    "$ -> User",
+                    "  \u{2026} 1 unchanged",
+                    "  $.name = \"a\"",
+                    "  $.age -> 3 (off by 1)",
+                    "  $.city = \"x\"",
+                    "  $.zip = 1",
+                    "  $.tags -> [..]",
+                    "    $.tags[0] removed \"a\"",
+                    "    $.tags[1] = \"b\"",
+                ]
+                .join("\n")
+            )
+        );
+    }
+}
@@ -0,0 +1,192 @@
+use colored::Colorize;
+
+use crate::{
+    change::Change,
+    parser::ValueKind,
+    pretty::{self, Notation, Row},
+};
+
+/// Lines around a change that a hunk shows
+const CONTEXT: usize = 3;
+
+/// Pretty prints both values and writes their difference as a unified diff.
+/// Lines that only differ where the options allow it are written like the expected value has them
+pub fn unified(
+    expected: &ValueKind,
+    real: &ValueKind,
+    changes: &[Change],
+    color: bool,
+    notation: Notation,
+) -> String {
+    let left = pretty::lines(expected, notation);
+    let right = pretty::lines(real, notation);
+    let mut lines = vec![];
+    let (mut removed, mut added) = (vec![], vec![]);
+    for row in pretty::rows(&left, &right, changes) {
+        match row {
+            Row::Changed(l, r) => {
+                removed.extend(l.map(|l| ('-', l.text.as_str())));
+                added.extend(r.map(|r| ('+', r.text.as_str())));
+            }
+            Row::Same(l, _) => {
+                lines.append(&mut removed);
+                lines.append(&mut added);
+                lines.push((' ', l.text.as_str()));
+            }
+        }
+    }
+    lines.append(&mut removed);
+    lines.append(&mut added);
+
+    let mut out = vec!["--- expected".to_string(), "+++ actual".to_string()];
+    for (start, end) in hunks(&lines) {
+        let before = &lines[..start];
+        let hunk = &lines[start..end];
+        let old = |l: &&(char, &str)| l.0 != '+';
+        let new = |l: &&(char, &str)| l.0 != '-';
+        out.push(format!(
+            "@@ -{} +{} @@",
+            range(
+                before.iter().filter(old).count(),
+                hunk.iter().filter(old).count()
+            ),
+            range(
+                before.iter().filter(new).count(),
+                hunk.iter().filter(new).count()
+            )
+        ));
+        out.extend(hunk.iter().map(|(sign, line)| format!("{sign}{line}")));
+    }
+    if color {
+        for line in out.iter_mut() {
+            *line = match line.chars().next() {
+                Some('-') => line.red().to_string(),
+                Some('+') => line.green().to_string(),
+                Some('@') => line.cyan().to_string(),
+                _ => line.to_string(),
+            };
+        }
+    }
+    out.into_iter().map(|line| format!("\n{line}")).collect()
+}
+
+/// Start and end of the lines around changes. Changes that are close share a hunk
+fn hunks(lines: &[(char, &str)]) -> Vec<(usize, usize)> {
+    let mut out: Vec<(usize, usize)> = vec![];
+    for (i, _) in lines.iter().enumerate().filter(|(_, l)| l.0 != ' ') {
+        let start = i.saturating_sub(CONTEXT);
+        let end = (i + 1 + CONTEXT).min(lines.len());
+        match out.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => out.push((start, end)),
+        }
+    }
+    out
+}
+
+/// Line numbers of a hunk: the first line and the amount of lines
+fn range(before: usize, len: usize) -> String {
+    match len {
+        0 => format!("{before},0"),
+        _ => format!("{},{len}", before + 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{get_hint_with_format, DiffOptions, Format, Notation};
+    use pretty_assertions::assert_eq;
+
+    fn unified(text: &str, notation: Notation) -> Option<String> {
+        let format = Format::Unified { notation };
+        get_hint_with_format(text, false, &DiffOptions::default(), format)
+    }
+
+    #[test]
+    fn unified_hunks() {
+        assert_eq!(
+            unified(
+                "org.opentest4j.AssertionFailedError: expected: <User(a=1, b=2, c=3, d=4, e=5, f=6, g=7, h=8, i=9, j=10)> but was: <User(a=0, b=2, c=3, d=4, e=5, f=6, g=7, h=8, j=10, k=11)>",
+                Notation::ToString
+            ),
+            Some(
+                [
+                    "",
+                    "--- expected",
+                    "+++ actual",
+                    "@@ -1,5 +1,5 @@",
+                    " User(",
+                    "-  a=1",
+                    "+  a=0",
+                    "   b=2",
+                    "   c=3",
+                    "   d=4",
+                    "@@ -7,6 +7,6 @@",
+                    "   f=6",
+                    "   g=7",
+                    "   h=8",
+                    "-  i=9",
+                    "   j=10",
+                    "+  k=11",
+                    " )",
+                ]
+                .join("\n")
+            )
+        );
+    }
+
+    #[test]
+    fn unified_json() {
+        assert_eq!(
+            unified(
+                "org.opentest4j.AssertionFailedError: expected: <User(id=1)> but was: <User(id=2)>",
+                Notation::Json
+            ),
+            Some(
+                [
+                    "",
+                    "--- expected",
+                    "+++ actual",
+                    "@@ -1,3 +1,3 @@",
+                    " {",
+                    "-  \"id\": 1",
+                    "+  \"id\": 2",
+                    " }",
+                ]
+                .join("\n")
+            )
+        );
+    }
+
+    #[test]
+    fn unified_ignore() {
+        let options = DiffOptions {
+            ignore: vec!["ts".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            get_hint_with_format(
+                "org.opentest4j.AssertionFailedError: expected: <User(ts=5, name=a \"b\")> but was: <User(ts=6, name=b)>",
+                false,
+                &options,
+                Format::Unified {
+                    notation: Notation::ToString
+                }
+            ),
+            Some(
+                [
+                    "",
+                    "--- expected",
+                    "+++ actual",
+                    "@@ -1,4 +1,4 @@",
+                    " User(",
+                    "   ts=5",
+                    "-  name=\"a \\\"b\\\"\"",
+                    "+  name=\"b\"",
+                    " )",
+                ]
+                .join("\n")
+            )
+        );
+    }
+}